@@ -0,0 +1,290 @@
+use crate::normalize::context_before;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashMap;
+
+/// All 77 provinces with their names and postcode prefixes. Districts and subdistricts are a partial
+/// list (Bangkok and the larger provinces), not the full DOPA register.
+const BUNDLED_GAZETTEER: &str = include_str!("../data/thai_gazetteer.txt");
+
+lazy_static! {
+    // Address keywords, full and abbreviated: ซอยสุขุมวิท 21, ม.5, ต.สุเทพ, อ.เมืองเชียงใหม่, จ.เชียงใหม่
+    static ref RE_MARKER: Regex = Regex::new(
+        r"บ้านเลขที่|เลขที่|หมู่ที่|หมู่|ม\.|ซอย|ซ\.|ถนน|ถ\.|แขวง|ตำบล|ต\.|เขต|อำเภอ|อ\.|จังหวัด|จ\."
+    ).unwrap();
+
+    // House, soi or moo number after a keyword and a space: ซอย 21, หมู่ 5, เลขที่ 99/1
+    // (never five digits, which would be the postcode)
+    static ref RE_NUMBER_TAIL: Regex = Regex::new(r"^ [0-9]{1,4}(?:/[0-9]+)?(?:[^0-9/]|$)").unwrap();
+
+    // House number written before the first keyword: 123/45 ซอย...
+    static ref RE_HOUSE_NUMBER: Regex = Regex::new(r"(?:^|[^0-9A-Za-z/])([0-9]+(?:/[0-9]+)?)[ \t]*$").unwrap();
+
+    static ref RE_POSTCODE: Regex = Regex::new(r"[1-9][0-9]{4}").unwrap();
+
+    static ref GAZETTEER: Gazetteer = Gazetteer::parse(BUNDLED_GAZETTEER);
+}
+
+/// Province lookups built from the bundled gazetteer.
+struct Gazetteer {
+    provinces: Vec<String>,
+    /// Province names and their short forms (กทม., โคราช)
+    names: HashMap<String, usize>,
+    /// Two-digit prefixes, or full postcodes where a prefix is shared (10 = Bangkok, 10270 = Samut Prakan)
+    postcodes: HashMap<String, usize>,
+    districts: HashMap<String, usize>,
+    subdistricts: HashMap<String, usize>,
+    re_names: Regex,
+}
+
+impl Gazetteer {
+    fn parse(source: &str) -> Self {
+        let mut gazetteer = Gazetteer {
+            provinces: Vec::new(),
+            names: HashMap::new(),
+            postcodes: HashMap::new(),
+            districts: HashMap::new(),
+            subdistricts: HashMap::new(),
+            re_names: Regex::new("$^").unwrap(),
+        };
+        let entries = |field: Option<&str>| -> Vec<String> {
+            field
+                .unwrap_or("")
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(str::to_string)
+                .collect()
+        };
+
+        for line in source.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let mut fields = line.split('|');
+            let names = entries(fields.next());
+            let Some(province) = names.first() else { continue };
+            let index = gazetteer.provinces.len();
+            gazetteer.provinces.push(province.clone());
+            gazetteer.districts.insert(format!("เมือง{}", province), index);
+            for name in names {
+                gazetteer.names.insert(name, index);
+            }
+            for postcode in entries(fields.next()) {
+                gazetteer.postcodes.insert(postcode, index);
+            }
+            for district in entries(fields.next()) {
+                gazetteer.districts.entry(district).or_insert(index);
+            }
+            for subdistrict in entries(fields.next()) {
+                gazetteer.subdistricts.entry(subdistrict).or_insert(index);
+            }
+        }
+
+        // Longest first, so กรุงเทพมหานคร wins over กรุงเทพ
+        let mut names: Vec<&String> = gazetteer.names.keys().collect();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let alternation: Vec<String> = names.iter().map(|name| regex::escape(name)).collect();
+        gazetteer.re_names = Regex::new(&alternation.join("|")).unwrap();
+        gazetteer
+    }
+
+    fn postcode(&self, code: &str) -> Option<usize> {
+        self.postcodes.get(code).or_else(|| self.postcodes.get(&code[..2])).copied()
+    }
+}
+
+/// How sure a piece of an address is about the province, best first.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+enum Evidence {
+    ProvinceName,
+    Postcode,
+    District,
+    Subdistrict,
+}
+
+/// One keyword with its value, a province name or a postcode.
+struct Part {
+    start: usize,
+    end: usize,
+    keyword: bool,
+    province: Option<(Evidence, usize)>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AddressMatch {
+    pub start: usize,
+    pub end: usize,
+    /// Province the address is in, by name, postcode, district or subdistrict
+    pub province: &'static str,
+}
+
+fn is_thai_letter(c: char) -> bool {
+    ('\u{0E01}'..='\u{0E2E}').contains(&c)
+}
+
+fn is_delimiter(c: char) -> bool {
+    c.is_whitespace() || matches!(c, ',' | ';' | '|' | '"' | '(' | ')')
+}
+
+fn keyword_parts(text: &str, gazetteer: &'static Gazetteer) -> Vec<Part> {
+    let markers: Vec<_> = RE_MARKER
+        .find_iter(text)
+        // ม. ต. อ. inside a word (กม., ผศ.) are not keywords
+        .filter(|m| !m.as_str().ends_with('.') || !text[..m.start()].ends_with(is_thai_letter))
+        .collect();
+
+    markers
+        .iter()
+        .enumerate()
+        .map(|(i, marker)| {
+            let next_marker = markers.get(i + 1).map_or(text.len(), |m| m.start());
+            let value_start = if text[marker.end()..].starts_with(' ') { marker.end() + 1 } else { marker.end() };
+            let value_end = text[value_start..next_marker.max(value_start)]
+                .find(is_delimiter)
+                .map_or(next_marker.max(value_start), |at| value_start + at);
+            let value = &text[value_start..value_end];
+
+            let (province, end) = match marker.as_str() {
+                "จังหวัด" | "จ." => (gazetteer.names.get(value).map(|&p| (Evidence::ProvinceName, p)), value_end),
+                "เขต" | "อำเภอ" | "อ." => (gazetteer.districts.get(value).map(|&p| (Evidence::District, p)), value_end),
+                "แขวง" | "ตำบล" | "ต." => {
+                    (gazetteer.subdistricts.get(value).map(|&p| (Evidence::Subdistrict, p)), value_end)
+                }
+                // ซอยสุขุมวิท 21, หมู่ 5
+                _ => {
+                    let tail = RE_NUMBER_TAIL.find(&text[value_end..]).map_or(0, |m| {
+                        m.as_str().trim_end_matches(|c: char| !c.is_ascii_digit()).len()
+                    });
+                    (None, value_end + tail)
+                }
+            };
+            Part {
+                start: marker.start(),
+                end,
+                keyword: true,
+                province,
+            }
+        })
+        .collect()
+}
+
+/// Thai postal addresses: runs of address keywords, province names and postcodes separated only
+/// by spaces or commas, with at least one keyword and a province the gazetteer can place.
+pub fn find_addresses(text: &str) -> Vec<AddressMatch> {
+    if !RE_MARKER.is_match(text) {
+        return Vec::new();
+    }
+    let gazetteer: &'static Gazetteer = &GAZETTEER;
+    let bytes = text.as_bytes();
+    let mut parts = keyword_parts(text, gazetteer);
+
+    // Bare province names, standing as their own word (not แพร่ in เผยแพร่)
+    for mat in gazetteer.re_names.find_iter(text) {
+        let before = text[..mat.start()].chars().next_back();
+        let after = text[mat.end()..].chars().next();
+        if before.is_none_or(|c| c.is_whitespace() || c == ',')
+            && after.is_none_or(|c| is_delimiter(c) || c.is_ascii_digit())
+        {
+            parts.push(Part {
+                start: mat.start(),
+                end: mat.end(),
+                keyword: false,
+                province: Some((Evidence::ProvinceName, gazetteer.names[mat.as_str()])),
+            });
+        }
+    }
+
+    for mat in RE_POSTCODE.find_iter(text) {
+        let glued = mat.start() > 0 && bytes[mat.start() - 1].is_ascii_digit()
+            || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_digit());
+        if let Some(province) = gazetteer.postcode(mat.as_str()).filter(|_| !glued) {
+            parts.push(Part {
+                start: mat.start(),
+                end: mat.end(),
+                keyword: false,
+                province: Some((Evidence::Postcode, province)),
+            });
+        }
+    }
+
+    parts.sort_by_key(|p| (p.start, std::cmp::Reverse(p.end)));
+    let mut kept: Vec<Part> = Vec::new();
+    for part in parts {
+        if kept.last().is_none_or(|last| part.start >= last.end) {
+            kept.push(part);
+        }
+    }
+
+    let mut found = Vec::new();
+    let mut group: Vec<&Part> = Vec::new();
+    for (i, part) in kept.iter().enumerate() {
+        group.push(part);
+        let joined = kept
+            .get(i + 1)
+            .is_some_and(|next| text[part.end..next.start].chars().all(|c| matches!(c, ' ' | '\t' | ',')));
+        if joined {
+            continue;
+        }
+        let province = group.iter().filter_map(|p| p.province).min();
+        if group.len() >= 2
+            && group.iter().any(|p| p.keyword)
+            && let Some((_, province)) = province
+        {
+            let mut start = group[0].start;
+            let context = context_before(text, start);
+            if let Some(number) = RE_HOUSE_NUMBER.captures(context).and_then(|c| c.get(1)) {
+                start -= context.len() - number.start();
+            }
+            found.push(AddressMatch {
+                start,
+                end: part.end,
+                province: &gazetteer.provinces[province],
+            });
+        }
+        group.clear();
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<(&str, &str)> {
+        find_addresses(text)
+            .iter()
+            .map(|m| (&text[m.start..m.end], m.province))
+            .collect()
+    }
+
+    #[test]
+    fn test_bangkok_and_upcountry_addresses() {
+        assert_eq!(
+            found("ส่งที่ 123/45 ซอยสุขุมวิท 21 แขวงคลองเตยเหนือ เขตวัฒนา กรุงเทพมหานคร 10110 โทรก่อนส่ง"),
+            vec![(
+                "123/45 ซอยสุขุมวิท 21 แขวงคลองเตยเหนือ เขตวัฒนา กรุงเทพมหานคร 10110",
+                "กรุงเทพมหานคร"
+            )]
+        );
+        assert_eq!(
+            found("99 ม.5 ต.สุเทพ อ.เมืองเชียงใหม่ จ.เชียงใหม่"),
+            vec![("99 ม.5 ต.สุเทพ อ.เมืองเชียงใหม่ จ.เชียงใหม่", "เชียงใหม่")]
+        );
+    }
+
+    #[test]
+    fn test_province_from_postcode_or_district() {
+        assert_eq!(found("ถนนนิมมานเหมินท์ 50200"), vec![("ถนนนิมมานเหมินท์ 50200", "เชียงใหม่")]);
+        assert_eq!(found("ถ.เทพารักษ์ 10270"), vec![("ถ.เทพารักษ์ 10270", "สมุทรปราการ")]);
+        assert_eq!(found("ซอย 3 อำเภอหาดใหญ่"), vec![("ซอย 3 อำเภอหาดใหญ่", "สงขลา")]);
+    }
+
+    #[test]
+    fn test_needs_a_keyword_and_a_known_place() {
+        // One keyword alone, an unknown district, a province name alone
+        assert!(found("ข้ามถนนไปเลย").is_empty());
+        assert!(found("ซอย 3 อำเภอไม่มีจริง").is_empty());
+        assert!(found("ไปเที่ยวเชียงใหม่ 3 วัน").is_empty());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::normalize::context_before;
+use crate::validator;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Singapore NRIC/FIN: S1234567D, confirmed by validator::is_sg_nric
+    static ref RE_SG_NRIC: Regex = Regex::new(r"[STFGM][0-9]{7}[A-Z]").unwrap();
+
+    // Malaysian MyKad: 900101-14-5678, confirmed by validator::is_my_mykad
+    static ref RE_MY_MYKAD: Regex = Regex::new(r"[0-9]{6}-[0-9]{2}-[0-9]{4}").unwrap();
+
+    // Undashed MyKad (900101145678): only after a keyword or under a matching key
+    static ref RE_MY_MYKAD_PLAIN: Regex = Regex::new(r"[0-9]{12}").unwrap();
+    static ref RE_MY_CONTEXT: Regex =
+        Regex::new(r"(?i)(mykad|\bic\b|i/c|\bkp\b|kad pengenalan|nric)").unwrap();
+
+    // Indonesian NIK: 16 digits, confirmed by validator::is_id_nik; only after a keyword or under a matching key
+    static ref RE_ID_NIK: Regex = Regex::new(r"[0-9]{16}").unwrap();
+    static ref RE_ID_CONTEXT: Regex = Regex::new(r"(?i)(\bnik\b|ktp|nomor induk|kependudukan)").unwrap();
+
+    // Vietnamese CCCD: 12 digits, confirmed by validator::is_vn_cccd; only after a keyword or under a matching key
+    static ref RE_VN_CCCD: Regex = Regex::new(r"[0-9]{12}").unwrap();
+    static ref RE_VN_CONTEXT: Regex =
+        Regex::new(r"(?i)(cccd|cmnd|căn cước|can cuoc|citizen.?id|national.?id)").unwrap();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum AseanIdKind {
+    SgNric,
+    MyMykad,
+    IdNik,
+    VnCccd,
+}
+
+impl AseanIdKind {
+    /// Detector that finds this kind, as named in the config.
+    pub fn detector(self) -> &'static str {
+        match self {
+            AseanIdKind::SgNric => "sg_nric",
+            AseanIdKind::MyMykad => "my_mykad",
+            AseanIdKind::IdNik => "id_nik",
+            AseanIdKind::VnCccd => "vn_cccd",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct AseanIdMatch {
+    pub start: usize,
+    pub end: usize,
+    pub kind: AseanIdKind,
+}
+
+/// Detector on/off, kind, candidate regex, validator, and the keyword the format needs nearby
+/// (`None` for formats whose structure or check digit is enough on its own).
+type Rule<'a> = (bool, AseanIdKind, &'a Regex, fn(&str) -> bool, Option<&'a Regex>);
+
+/// Which ASEAN ID detectors are on.
+pub struct AseanToggles {
+    pub sg_nric: bool,
+    pub my_mykad: bool,
+    pub id_nik: bool,
+    pub vn_cccd: bool,
+}
+
+/// A 16-digit NIK candidate that is also a valid card number is left to the card detector.
+fn is_id_nik(id: &str) -> bool {
+    !validator::is_card_number(id) && validator::is_id_nik(id)
+}
+
+/// Finds ASEAN national IDs that pass their validator, earliest first and never overlapping.
+/// MyKad is tried before CCCD since both are 12 digits and MyKad checks more structure.
+/// Bare digit runs (NIK, CCCD, undashed MyKad) need a keyword before them or a JSON `key` naming the ID.
+pub fn find_asean_ids(text: &str, toggles: &AseanToggles, key: Option<&str>) -> Vec<AseanIdMatch> {
+    let rules: [Rule; 5] = [
+        (toggles.sg_nric, AseanIdKind::SgNric, &RE_SG_NRIC, validator::is_sg_nric, None),
+        (toggles.id_nik, AseanIdKind::IdNik, &RE_ID_NIK, is_id_nik, Some(&RE_ID_CONTEXT)),
+        (toggles.my_mykad, AseanIdKind::MyMykad, &RE_MY_MYKAD, validator::is_my_mykad, None),
+        (toggles.my_mykad, AseanIdKind::MyMykad, &RE_MY_MYKAD_PLAIN, validator::is_my_mykad, Some(&RE_MY_CONTEXT)),
+        (toggles.vn_cccd, AseanIdKind::VnCccd, &RE_VN_CCCD, validator::is_vn_cccd, Some(&RE_VN_CONTEXT)),
+    ];
+
+    let bytes = text.as_bytes();
+    let mut found: Vec<AseanIdMatch> = Vec::new();
+    for (enabled, kind, re, is_valid, context) in rules {
+        if !enabled {
+            continue;
+        }
+        let keyed = context.is_some_and(|context| key.is_some_and(|key| context.is_match(&key.replace('_', " "))));
+        for mat in re.find_iter(text) {
+            // A piece of a longer number or word is not an ID
+            let glued = mat.start() > 0 && bytes[mat.start() - 1].is_ascii_alphanumeric()
+                || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_alphanumeric());
+            let overlaps = found.iter().any(|f| f.start < mat.end() && mat.start() < f.end);
+            let labelled = context.is_none_or(|context| keyed || context.is_match(context_before(text, mat.start())));
+            if !glued && !overlaps && labelled && is_valid(mat.as_str()) {
+                found.push(AseanIdMatch {
+                    start: mat.start(),
+                    end: mat.end(),
+                    kind,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|m| m.start);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_finds_each_country() {
+        let toggles = AseanToggles {
+            sg_nric: true,
+            my_mykad: true,
+            id_nik: true,
+            vn_cccd: true,
+        };
+        let text = "sg S1234567D my 900101-14-5678 NIK 3174054508900001 CCCD 079203012345 no S1234567A";
+        let kinds: Vec<AseanIdKind> = find_asean_ids(text, &toggles, None).iter().map(|m| m.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![AseanIdKind::SgNric, AseanIdKind::MyMykad, AseanIdKind::IdNik, AseanIdKind::VnCccd]
+        );
+    }
+
+    #[test]
+    fn test_bare_digit_runs_need_context() {
+        let toggles = AseanToggles {
+            sg_nric: true,
+            my_mykad: true,
+            id_nik: true,
+            vn_cccd: true,
+        };
+        assert!(find_asean_ids("order 3174054508900001 ref 079203012345 x 900101145678", &toggles, None).is_empty());
+        assert_eq!(find_asean_ids("079203012345", &toggles, Some("cccd_no")).len(), 1);
+        assert_eq!(find_asean_ids("ic 900101145678", &toggles, None)[0].kind, AseanIdKind::MyMykad);
+        // A Luhn-valid Mastercard number is a card, not a NIK, even after the keyword
+        assert!(validator::is_id_nik("5171011508900007"));
+        assert!(find_asean_ids("nik 5171011508900007", &toggles, None).is_empty());
+    }
+}
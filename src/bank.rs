@@ -0,0 +1,122 @@
+use crate::normalize::context_before;
+use crate::validator;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Candidate: 10-15 digits, optionally grouped with dashes
+    static ref RE_ACCOUNT_CANDIDATE: Regex = Regex::new(r"[0-9](?:-?[0-9]){9,14}").unwrap();
+
+    // Printed Thai bank layouts, recognised without any keyword
+    // xxx-x-xxxxx-x: KBank, SCB, BBL, KTB, ttb, Krungsri; xxx-x-xxxxx-xxx: GSB/BAAC 12-digit
+    static ref RE_BANK_LAYOUT: Regex =
+        Regex::new(r"^(?:[0-9]{3}-[0-9]-[0-9]{5}-[0-9]|[0-9]{3}-[0-9]-[0-9]{5}-[0-9]{3})$").unwrap();
+
+    // Keywords that make a bare digit run an account or PromptPay ID
+    static ref RE_ACCOUNT_CONTEXT: Regex =
+        Regex::new(r"(?i)(บัญชี|account|acct|a/c|bank)").unwrap();
+    static ref RE_PROMPTPAY_CONTEXT: Regex =
+        Regex::new(r"(?i)(พร้อมเพย์|prompt ?pay)").unwrap();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum BankKind {
+    /// Bank account number (10 or 12 digits)
+    Account,
+    /// PromptPay registered to a mobile number
+    PromptPayPhone,
+    /// PromptPay registered to a national ID (checked with is_thai_id)
+    PromptPayNationalId,
+    /// PromptPay e-Wallet ID (15 digits)
+    PromptPayWallet,
+}
+
+impl BankKind {
+    /// Detector that finds this kind, as named in the config.
+    pub fn detector(self) -> &'static str {
+        match self {
+            BankKind::Account => "bank_account",
+            BankKind::PromptPayPhone | BankKind::PromptPayNationalId | BankKind::PromptPayWallet => "promptpay",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct BankMatch {
+    pub start: usize,
+    pub end: usize,
+    pub kind: BankKind,
+}
+
+fn classify(candidate: &str, context: &str, account: bool, promptpay: bool) -> Option<BankKind> {
+    if account && candidate.contains('-') && RE_BANK_LAYOUT.is_match(candidate) {
+        return Some(BankKind::Account);
+    }
+
+    let digits: String = candidate.chars().filter(|c| c.is_ascii_digit()).collect();
+    if promptpay && RE_PROMPTPAY_CONTEXT.is_match(context) {
+        match digits.len() {
+            10 if digits.starts_with('0') => return Some(BankKind::PromptPayPhone),
+            13 if validator::is_thai_id(&digits) => return Some(BankKind::PromptPayNationalId),
+            15 => return Some(BankKind::PromptPayWallet),
+            _ => {}
+        }
+    }
+    if account && RE_ACCOUNT_CONTEXT.is_match(context) && (digits.len() == 10 || digits.len() == 12) {
+        return Some(BankKind::Account);
+    }
+    None
+}
+
+/// Finds bank account numbers and PromptPay IDs in `text`.
+pub fn find_bank_ids(text: &str, account: bool, promptpay: bool) -> Vec<BankMatch> {
+    // Without any keyword only the printed layouts can match
+    let promptpay = promptpay && RE_PROMPTPAY_CONTEXT.is_match(text);
+    if !promptpay && !(account && (text.contains('-') || RE_ACCOUNT_CONTEXT.is_match(text))) {
+        return Vec::new();
+    }
+    let bytes = text.as_bytes();
+    RE_ACCOUNT_CANDIDATE
+        .find_iter(text)
+        .filter(|mat| {
+            let glued = mat.start() > 0 && bytes[mat.start() - 1].is_ascii_alphanumeric()
+                || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'-');
+            !glued
+        })
+        .filter_map(|mat| {
+            let kind = classify(mat.as_str(), context_before(text, mat.start()), account, promptpay)?;
+            Some(BankMatch {
+                start: mat.start(),
+                end: mat.end(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(text: &str) -> Vec<BankKind> {
+        find_bank_ids(text, true, true).iter().map(|m| m.kind).collect()
+    }
+
+    #[test]
+    fn test_bank_layouts_and_keywords() {
+        assert_eq!(kinds("โอนเข้า 123-4-56789-0"), vec![BankKind::Account]);
+        assert_eq!(kinds("GSB 020-1-23456-789"), vec![BankKind::Account]);
+        assert_eq!(kinds("เลขบัญชี 1234567890"), vec![BankKind::Account]);
+        assert_eq!(kinds("account_no=123456789012"), vec![BankKind::Account]);
+        // No keyword, no layout: just a number
+        assert!(kinds("order 1234567890").is_empty());
+    }
+
+    #[test]
+    fn test_promptpay_ids() {
+        assert_eq!(kinds("PromptPay: 0812345678"), vec![BankKind::PromptPayPhone]);
+        assert_eq!(kinds("พร้อมเพย์ 1103700012346"), vec![BankKind::PromptPayNationalId]);
+        assert_eq!(kinds("promptpay 1103700012345"), Vec::<BankKind>::new());
+        assert_eq!(kinds("promptpay ewallet 140000012345678"), vec![BankKind::PromptPayWallet]);
+    }
+}
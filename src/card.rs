@@ -0,0 +1,99 @@
+use crate::validator;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+lazy_static! {
+    // Credit Card: Matches sequence of digits/spaces/dashes, length 13-20
+    static ref RE_CREDIT_CARD: Regex = Regex::new(r"([0-9][ -]*?){13,20}").unwrap();
+
+    // CVV next to a card: "cvv: 123", "CVC2=1234", "security code 123"
+    static ref RE_CVV: Regex = Regex::new(
+        r#"(?i)\b(?:cvv2?|cvc2?|csc|cid|security code)["']?\s*[:=]?\s*["']?([0-9]{3,4})\b"#
+    ).unwrap();
+
+    // Expiry next to a card: "exp 12/27", "expiry_date: 01/2028", "valid thru 12 / 27", "วันหมดอายุ 12/27"
+    static ref RE_EXPIRY: Regex = Regex::new(
+        r#"(?i)(?:\bexp(?:iry|iration)?(?:[ _]?date)?|\bvalid thru|วันหมดอายุ)["']?\s*[:=]?\s*["']?((?:0[1-9]|1[0-2]) ?/ ?(?:[0-9]{4}|[0-9]{2}))\b"#
+    ).unwrap();
+}
+
+/// How far from a card number a CVV or expiry is still treated as belonging to it.
+const DETAIL_WINDOW_BYTES: usize = 64;
+
+/// JSON keys holding a card's CVV or expiry.
+const DETAIL_KEYS: &[&str] = &[
+    "cvv", "cvv2", "cvc", "cvc2", "csc", "cid", "securitycode", "exp", "expiry", "expirydate", "expdate",
+    "expiration", "expirationdate", "expmonth", "expyear",
+];
+
+/// Card numbers in `text`: right length for their network (IIN/BIN) and Luhn-valid.
+pub fn find_cards(text: &str) -> Vec<Range<usize>> {
+    RE_CREDIT_CARD
+        .find_iter(text)
+        .filter(|mat| validator::is_card_number(mat.as_str()))
+        .map(|mat| mat.range())
+        .collect()
+}
+
+pub fn contains_card(text: &str) -> bool {
+    !find_cards(text).is_empty()
+}
+
+/// CVV and expiry values within `DETAIL_WINDOW_BYTES` of one of `cards`, with their masks.
+pub fn find_card_details(text: &str, cards: &[Range<usize>]) -> Vec<(Range<usize>, String)> {
+    let near_card = |range: &Range<usize>| {
+        cards.iter().any(|card| {
+            range.start < card.end + DETAIL_WINDOW_BYTES && card.start < range.end + DETAIL_WINDOW_BYTES
+        })
+    };
+
+    let mut details = Vec::new();
+    for caps in RE_CVV.captures_iter(text) {
+        let value = caps.get(1).map_or(0..0, |m| m.range());
+        if near_card(&value) {
+            details.push((value, "***".to_string()));
+        }
+    }
+    for caps in RE_EXPIRY.captures_iter(text) {
+        let Some(value) = caps.get(1) else { continue };
+        if near_card(&value.range()) {
+            // 12/27 -> **/**
+            let masked = value.as_str().chars().map(|c| if c.is_ascii_digit() { '*' } else { c }).collect();
+            details.push((value.range(), masked));
+        }
+    }
+    details
+}
+
+/// `cvv`, `exp_date`, `Expiry-Date`, ... whatever the casing and separators.
+pub fn is_card_detail_key(key: &str) -> bool {
+    let compact: String = key
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    DETAIL_KEYS.contains(&compact.as_str())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_order_numbers_passing_luhn_are_not_cards() {
+        assert!(find_cards("order 1000000000000008").is_empty());
+        assert_eq!(find_cards("card 4532-0151-1283-0366"), vec![5..24]);
+    }
+
+    #[test]
+    fn test_details_only_near_a_card() {
+        let text = "card 4532015112830366 exp 12/27 cvv: 123";
+        let cards = find_cards(text);
+        let details: Vec<&str> = find_card_details(text, &cards).iter().map(|(r, _)| &text[r.clone()]).collect();
+        assert_eq!(details, vec!["123", "12/27"]);
+
+        assert!(find_card_details("otp cvv 123", &[]).is_empty());
+        assert!(is_card_detail_key("Expiry-Date"));
+    }
+}
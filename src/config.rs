@@ -1,33 +1,571 @@
+use crate::denylist::DenyList;
+use crate::field_rules::{FieldAction, FieldRule};
+use crate::names::NameDictionary;
+use crate::scoring;
+use crate::terms::TermList;
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use std::sync::Arc;
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct AppConfig {
     pub server: ServerConfig,
     pub target: TargetConfig,
-    #[allow(dead_code)]
+    /// Default masking policy, used when no named policy is selected.
     pub masking: MaskingConfig,
+    #[serde(default)]
+    pub policies: PoliciesConfig,
+    #[serde(default)]
+    pub limits: LimitsConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServerConfig {
     pub port: u16,
     pub host: String,
+    #[serde(default = "default_max_body_bytes")]
+    pub max_body_bytes: usize,
+    #[serde(default)]
+    pub tls: Option<ServerTlsConfig>,
+}
+
+/// TLS termination for the inbound listener (rustls).
+#[derive(Debug, Deserialize, Clone)]
+pub struct ServerTlsConfig {
+    pub cert_path: String,
+    pub key_path: String,
+    /// How often the cert/key (and CA bundle) files are checked for changes.
+    #[serde(default = "default_tls_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+    #[serde(default)]
+    pub client_auth: Option<ClientAuthConfig>,
+}
+
+/// Client certificate verification (mTLS) for the inbound listener.
+#[derive(Debug, Deserialize, Clone)]
+pub struct ClientAuthConfig {
+    pub ca_path: String,
+    /// When false, clients without a certificate are still accepted (anonymous).
+    #[serde(default = "default_true")]
+    pub required: bool,
+    /// Maps a client cert subject (full DN like "CN=card-svc,O=Acme", in any RDN order)
+    /// to a policy identity. Unmapped certificates get no identity.
+    #[serde(default)]
+    pub identities: HashMap<String, String>,
+}
+
+fn default_max_body_bytes() -> usize {
+    2 * 1024 * 1024 // 2MB
+}
+
+fn default_tls_reload_interval_secs() -> u64 {
+    30
+}
+
+fn default_true() -> bool {
+    true
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct TargetConfig {
     pub url: String,
     pub timeout_ms: u64,
+    #[serde(default)]
+    pub tls: Option<UpstreamTlsConfig>,
+}
+
+/// TLS settings for the upstream client.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct UpstreamTlsConfig {
+    /// Extra CA bundle (PEM) trusted in addition to the built-in roots.
+    #[serde(default)]
+    pub ca_path: Option<String>,
+    /// Set to false to trust only `ca_path` (private PKI).
+    #[serde(default = "default_true")]
+    pub use_builtin_roots: bool,
+    /// Client certificate and key (PEM) presented to the upstream for mTLS.
+    #[serde(default)]
+    pub client_cert_path: Option<String>,
+    #[serde(default)]
+    pub client_key_path: Option<String>,
+    /// Server name sent in SNI and checked against the certificate,
+    /// while still connecting to the host from `target.url`.
+    #[serde(default)]
+    pub sni: Option<String>,
+    /// "1.2" or "1.3"
+    #[serde(default)]
+    pub min_version: Option<String>,
+    /// Skips certificate verification. Local testing only.
+    #[serde(default)]
+    pub insecure_skip_verify: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct MaskingConfig {
-    #[allow(dead_code)]
+    #[serde(default)]
     pub exclude_fields: Vec<String>,
-    #[allow(dead_code)]
+    #[serde(default = "default_max_depth")]
+    pub max_depth: u8,
+    /// JSON fields picked by path (`$.customer.*.email`, `**.password`) or key glob (`*_nm`),
+    /// first match wins; checked before any key or value detector.
+    #[serde(default)]
+    pub field_rules: Vec<FieldRule>,
+    /// Secret key for `hash` field rules (HMAC-SHA256), unique per deployment so hashes of
+    /// guessable values (phone numbers, IDs) can't be reversed by enumeration.
+    #[serde(default)]
+    pub hash_key: Option<String>,
+    /// Default rule after `field_rules`: keys containing "name" or "user" get `strategies.name`.
+    #[serde(default = "default_true")]
+    pub name_key_rule: bool,
+    #[serde(default)]
+    pub detectors: DetectorConfig,
+    #[serde(default)]
+    pub strategies: StrategyConfig,
+    /// Longest line buffered while waiting for a newline.
+    #[serde(default = "default_max_line_bytes")]
+    pub max_line_bytes: usize,
+    /// What to do with a line longer than `max_line_bytes`.
+    #[serde(default)]
+    pub oversized_line: OversizedLinePolicy,
+    /// Longest JSON string value run through the detectors; longer ones are redacted whole.
+    #[serde(default = "default_max_string_bytes")]
+    pub max_string_bytes: usize,
+    /// Network prefix kept when masking IP addresses.
+    #[serde(default)]
+    pub network_prefix: NetworkPrefixConfig,
+    /// Keyword-proximity confidence and the threshold a detection must reach to be masked.
+    #[serde(default)]
+    pub scoring: ScoringConfig,
+    /// Encoded values (JSON in a string, percent-encoding, base64) looked into before masking.
+    #[serde(default)]
+    pub decode: DecodeConfig,
+    /// Width of the age bands dates of birth are generalised to.
+    #[serde(default = "default_age_band_years")]
+    pub age_band_years: u8,
+    /// Extra given names and surnames for the free-text name detector, one per line.
+    #[serde(default)]
+    pub name_list_path: Option<String>,
+    /// Bundled names plus `name_list_path`, filled in by `AppConfig::load`.
+    #[serde(skip, default = "NameDictionary::bundled")]
+    pub name_dictionary: Arc<NameDictionary>,
+    /// Values never masked (test cards, fake IDs), by detectors or by key rules such as name and
+    /// password keys; spaces, dashes and case are ignored. `field_rules` still apply to them.
+    #[serde(default)]
+    pub allow_values: Vec<String>,
+    /// File of SHA-256 hashes of values masked wherever they appear, one hex digest per line
+    /// (followed by the length in characters for Thai values).
+    #[serde(default)]
+    pub deny_list_path: Option<String>,
+    /// Hashes read from `deny_list_path` by `AppConfig::load`.
+    #[serde(skip)]
+    pub deny_list: Arc<DenyList>,
+    /// Literal terms (codenames, VIP and partner names) masked wherever they appear.
+    #[serde(default)]
+    pub terms: Option<TermsConfig>,
+    /// Dictionary built from `terms` by `AppConfig::load`, rebuilt when its file changes.
+    #[serde(skip)]
+    pub term_list: Option<Arc<TermList>>,
+}
+
+impl Default for MaskingConfig {
+    fn default() -> Self {
+        Self {
+            exclude_fields: vec![],
+            max_depth: default_max_depth(),
+            field_rules: Vec::new(),
+            hash_key: None,
+            name_key_rule: true,
+            detectors: DetectorConfig::default(),
+            strategies: StrategyConfig::default(),
+            max_line_bytes: default_max_line_bytes(),
+            oversized_line: OversizedLinePolicy::default(),
+            max_string_bytes: default_max_string_bytes(),
+            network_prefix: NetworkPrefixConfig::default(),
+            scoring: ScoringConfig::default(),
+            decode: DecodeConfig::default(),
+            age_band_years: default_age_band_years(),
+            name_list_path: None,
+            name_dictionary: NameDictionary::bundled(),
+            allow_values: Vec::new(),
+            deny_list_path: None,
+            deny_list: Arc::default(),
+            terms: None,
+            term_list: None,
+        }
+    }
+}
+
+impl MaskingConfig {
+    fn check_hash_key(&self) -> Result<(), String> {
+        let hashes = self.field_rules.iter().any(|rule| rule.action == FieldAction::Hash);
+        if hashes && self.hash_key.as_ref().is_none_or(|key| key.len() < MIN_HASH_KEY_BYTES) {
+            return Err(format!(
+                "field_rules with action hash need a hash_key of at least {} bytes",
+                MIN_HASH_KEY_BYTES
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Shortest `hash_key` accepted for HMAC-SHA256.
+const MIN_HASH_KEY_BYTES: usize = 16;
+
+/// Prefix lengths kept by the IP masks, so traffic can still be grouped by subnet.
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct NetworkPrefixConfig {
+    pub ipv4: u8,
+    pub ipv6: u8,
+}
+
+impl Default for NetworkPrefixConfig {
+    fn default() -> Self {
+        Self { ipv4: 24, ipv6: 48 }
+    }
+}
+
+impl NetworkPrefixConfig {
+    fn check(&self) -> Result<(), String> {
+        if self.ipv4 > 32 {
+            return Err(format!("network_prefix.ipv4 must be at most 32, got {}", self.ipv4));
+        }
+        if self.ipv6 > 128 {
+            return Err(format!("network_prefix.ipv6 must be at most 128, got {}", self.ipv6));
+        }
+        Ok(())
+    }
+}
+
+/// Bounds on decoding nested payloads; what is masked inside is re-encoded the same way.
+#[derive(Debug, Deserialize, Clone, Copy)]
+#[serde(default)]
+pub struct DecodeConfig {
+    /// Encoding layers to look through; 0 turns decoding off.
     pub max_depth: u8,
+    /// Encoded values longer than this are left as they are.
+    pub max_bytes: usize,
+}
+
+impl Default for DecodeConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: 3,
+            max_bytes: 64 * 1024,
+        }
+    }
+}
+
+/// Term list for the dictionary detector: a file of one term per line and/or terms inline.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct TermsConfig {
+    pub path: Option<String>,
+    pub values: Vec<String>,
+    pub case_insensitive: bool,
+    /// Fold accented Latin letters to their base letter and drop Thai tone marks before matching.
+    pub ignore_diacritics: bool,
+    /// How often `path` is checked for changes.
+    pub reload_interval_secs: u64,
+}
+
+impl Default for TermsConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            values: Vec::new(),
+            case_insensitive: true,
+            ignore_diacritics: true,
+            reload_interval_secs: 30,
+        }
+    }
+}
+
+impl TermsConfig {
+    fn check(&self) -> Result<(), String> {
+        if self.reload_interval_secs == 0 {
+            return Err("terms.reload_interval_secs must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Each detection starts at 0.6; a boosting keyword before it or in its JSON key adds 0.4,
+/// a penalising one takes 0.4 off. Detections under `threshold` are left unmasked.
+/// Penalties are opt-in and never apply to checksum-validated detectors (thai_id, credit_card,
+/// sg_nric, iban, aba_routing, bitcoin_address) or to mixed-case EIP-55 Ethereum addresses.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct ScoringConfig {
+    /// 0 masks every detection, 0.5 skips those next to a configured penalising keyword only,
+    /// 0.7 needs a boosting keyword.
+    pub threshold: f32,
+    /// Keywords per detector, replacing its built-in boosting words.
+    pub keywords: HashMap<String, KeywordConfig>,
+}
+
+impl Default for ScoringConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.5,
+            keywords: HashMap::new(),
+        }
+    }
+}
+
+impl ScoringConfig {
+    fn check(&self) -> Result<(), String> {
+        if !(0.0..=1.0).contains(&self.threshold) {
+            return Err(format!("scoring.threshold must be between 0 and 1, got {}", self.threshold));
+        }
+        if let Some(unknown) = self.keywords.keys().find(|name| !scoring::DETECTORS.contains(&name.as_str())) {
+            return Err(format!("scoring.keywords: unknown detector '{}'", unknown));
+        }
+        if let Some((name, _)) = self
+            .keywords
+            .iter()
+            .find(|(name, keywords)| scoring::CHECKSUM_VALIDATED.contains(&name.as_str()) && !keywords.penalty.is_empty())
+        {
+            return Err(format!(
+                "scoring.keywords.{}: penalties don't apply to checksum-validated matches",
+                name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct KeywordConfig {
+    /// Words that make a nearby match more likely personal data (โทร, phone)
+    pub boost: Vec<String>,
+    /// Words that make it more likely a reference number (order, ใบเสร็จ)
+    pub penalty: Vec<String>,
+}
+
+fn default_max_depth() -> u8 {
+    20
+}
+
+fn default_age_band_years() -> u8 {
+    10
+}
+
+/// Leaves room for the overlap window kept by a forced flush.
+const MIN_LINE_BYTES: usize = 1024;
+
+fn default_max_line_bytes() -> usize {
+    1024 * 1024 // 1MB
+}
+
+fn default_max_string_bytes() -> usize {
+    64 * 1024 // 64KB
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum OversizedLinePolicy {
+    /// Mask what is buffered so far, cutting where no value crosses; JSON lines, and text
+    /// with no such cut, are redacted like `Redact`
+    #[default]
+    Flush,
+    /// Abort the request with 413 Payload Too Large
+    Reject,
+    /// Replace the rest of the line with a marker, resume at the next newline
+    Redact,
+}
+
+/// Turns individual detectors on or off. Everything is on by default.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct DetectorConfig {
+    pub thai_id: bool,
+    pub credit_card: bool,
+    pub email: bool,
+    pub phone: bool,
+    /// Names after an honorific in free text, and `name_key_rule` for JSON keys
+    pub name: bool,
+    /// AWS access key IDs and secret access keys
+    pub aws_key: bool,
+    pub github_token: bool,
+    pub gitlab_token: bool,
+    pub slack_token: bool,
+    pub stripe_key: bool,
+    /// JWTs whose header decodes to a JSON object with "alg"
+    pub jwt: bool,
+    /// PEM private key blocks
+    pub private_key: bool,
+    /// password=/db_passwd:/user_pwd pairs and JSON keys ending in password/passwd/pwd
+    pub password: bool,
+    /// user:password@ inside URLs
+    pub url_credentials: bool,
+    /// IPv4 and IPv6 addresses
+    pub ip_address: bool,
+    pub mac_address: bool,
+    /// Thai bank account numbers (printed layout, or a bare number after บัญชี/account)
+    pub bank_account: bool,
+    /// PromptPay phone, national ID and e-Wallet IDs after พร้อมเพย์/promptpay
+    pub promptpay: bool,
+    /// Thai document numbers, each only after its keyword (passport, ใบขับขี่, ทะเบียน, ประกันสังคม)
+    pub passport: bool,
+    pub driver_license: bool,
+    pub vehicle_plate: bool,
+    pub social_security: bool,
+    /// ASEAN national IDs, each gated by its validator (check letter, birth date, region code);
+    /// bare digit runs (NIK, CCCD, undashed MyKad) also need a keyword or matching JSON key
+    pub sg_nric: bool,
+    pub my_mykad: bool,
+    pub id_nik: bool,
+    pub vn_cccd: bool,
+    /// IBAN (mod-97); SWIFT/BIC and ABA routing numbers only after swift/bic/routing
+    pub iban: bool,
+    pub swift_bic: bool,
+    pub aba_routing: bool,
+    /// Thai postal addresses placed by the bundled gazetteer, generalised to their province
+    pub address: bool,
+    /// Dates (CE or BE, numeric or with a month name) after dob/birth/วันเกิด, and in birth date JSON keys
+    pub date_of_birth: bool,
+    /// URLs and request paths: query parameters masked by name like JSON fields (field rules apply),
+    /// path segments and fragments as text
+    pub url: bool,
+    /// Bitcoin (Base58Check, Bech32/Bech32m) and Ethereum (EIP-55) addresses, by checksum
+    pub bitcoin_address: bool,
+    pub ethereum_address: bool,
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            thai_id: true,
+            credit_card: true,
+            email: true,
+            phone: true,
+            name: true,
+            aws_key: true,
+            github_token: true,
+            gitlab_token: true,
+            slack_token: true,
+            stripe_key: true,
+            jwt: true,
+            private_key: true,
+            password: true,
+            url_credentials: true,
+            ip_address: true,
+            mac_address: true,
+            bank_account: true,
+            promptpay: true,
+            passport: true,
+            driver_license: true,
+            vehicle_plate: true,
+            social_security: true,
+            sg_nric: true,
+            my_mykad: true,
+            id_nik: true,
+            vn_cccd: true,
+            iban: true,
+            swift_bic: true,
+            aba_routing: true,
+            address: true,
+            date_of_birth: true,
+            url: true,
+            bitcoin_address: true,
+            ethereum_address: true,
+        }
+    }
+}
+
+/// How each detector rewrites what it found.
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct StrategyConfig {
+    pub thai_id: MaskStrategy,
+    pub credit_card: MaskStrategy,
+    pub email: MaskStrategy,
+    pub phone: MaskStrategy,
+    pub name: MaskStrategy,
+    pub ip_address: MaskStrategy,
+    pub mac_address: MaskStrategy,
+    pub bank_account: MaskStrategy,
+    pub promptpay: MaskStrategy,
+    pub passport: MaskStrategy,
+    pub driver_license: MaskStrategy,
+    pub vehicle_plate: MaskStrategy,
+    pub social_security: MaskStrategy,
+    pub sg_nric: MaskStrategy,
+    pub my_mykad: MaskStrategy,
+    pub id_nik: MaskStrategy,
+    pub vn_cccd: MaskStrategy,
+    pub iban: MaskStrategy,
+    pub swift_bic: MaskStrategy,
+    pub aba_routing: MaskStrategy,
+    pub address: MaskStrategy,
+    pub date_of_birth: MaskStrategy,
+    pub term: MaskStrategy,
+    pub bitcoin_address: MaskStrategy,
+    pub ethereum_address: MaskStrategy,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum MaskStrategy {
+    /// Detector's built-in partial mask (e.g. 081XXXXX78)
+    #[default]
+    Partial,
+    /// Hide everything except the last 4 letters/digits, separators are kept
+    KeepLast4,
+    /// Replace the whole value with [REDACTED]
+    Redact,
+    /// IP addresses: network address in CIDR form (192.168.1.0/24); other detectors use partial
+    Truncate,
+    /// Cards: PCI DSS first 6 / last 4 (453201******0366); other detectors use partial
+    Pci,
+    /// Dates of birth: the year alone (2530); other detectors use partial
+    Year,
+    /// Dates of birth: the age band, `age_band_years` wide (30-39); other detectors use partial
+    AgeBand,
+}
+
+/// Per-client rate limiting and stream concurrency caps. Unset means unlimited.
+/// Clients are keyed by a configured `X-Api-Key`, then the mTLS identity, falling back to the peer IP.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct LimitsConfig {
+    #[serde(default)]
+    pub requests_per_sec: Option<f64>,
+    /// Bucket size; defaults to `requests_per_sec`.
+    #[serde(default)]
+    pub burst: Option<f64>,
+    /// Global cap on concurrent `/mask` streams.
+    #[serde(default)]
+    pub max_in_flight: Option<usize>,
+    #[serde(default)]
+    pub max_in_flight_per_client: Option<usize>,
+}
+
+/// Named masking policies and how a request picks one.
+/// Precedence: API key / client identity, then `X-Iron-Mask-Policy` header, `/mask/{policy}` route, default.
+/// A mapped key or identity can only switch policy with the header to one listed in `header_policies`.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct PoliciesConfig {
+    /// Policy used when nothing selects one. Empty means the `masking` section.
+    #[serde(default)]
+    pub default: Option<String>,
+    /// Refuse requests naming an unknown policy instead of falling back to the default.
+    #[serde(default)]
+    pub reject_unknown: bool,
+    /// `X-Api-Key` value -> policy name
+    #[serde(default)]
+    pub api_keys: HashMap<String, String>,
+    /// mTLS client identity -> policy name
+    #[serde(default)]
+    pub identities: HashMap<String, String>,
+    /// API key or client identity -> other policies it may pick with the header
+    #[serde(default)]
+    pub header_policies: HashMap<String, Vec<String>>,
+    #[serde(default)]
+    pub definitions: HashMap<String, MaskingConfig>,
 }
 
 #[derive(Debug)]
@@ -62,31 +600,76 @@ impl AppConfig {
         } else {
              // Fallback default if no config file (useful for pure Docker/Env usage)
              AppConfig {
-                 server: ServerConfig { port: 3000, host: "0.0.0.0".to_string() },
-                 target: TargetConfig { url: "http://localhost:8080".to_string(), timeout_ms: 5000 },
-                 masking: MaskingConfig { exclude_fields: vec![], max_depth: 20 },
+                 server: ServerConfig {
+                     port: 3000,
+                     host: "0.0.0.0".to_string(),
+                     max_body_bytes: default_max_body_bytes(),
+                     tls: None,
+                 },
+                 target: TargetConfig { url: "http://localhost:8080".to_string(), timeout_ms: 5000, tls: None },
+                 masking: MaskingConfig::default(),
+                 policies: PoliciesConfig::default(),
+                 limits: LimitsConfig::default(),
              }
         };
 
         // 2. Override with Environment Variables (Cloud Native)
-        if let Ok(port) = std::env::var("PORT") {
-            if let Ok(p) = port.parse() { config.server.port = p; }
+        if let Ok(port) = std::env::var("PORT")
+            && let Ok(p) = port.parse()
+        {
+            config.server.port = p;
         }
         if let Ok(url) = std::env::var("TARGET_URL") {
              config.target.url = url;
         } else if let Ok(url) = std::env::var("TARGET_LOG_URL") {
              config.target.url = url;
         }
-        if let Ok(depth) = std::env::var("MASKING_MAX_DEPTH") {
-            if let Ok(d) = depth.parse() { config.masking.max_depth = d; }
+        if let Ok(depth) = std::env::var("MASKING_MAX_DEPTH")
+            && let Ok(d) = depth.parse()
+        {
+            config.masking.max_depth = d;
+        }
+        if let Ok(key) = std::env::var("MASKING_HASH_KEY") {
+            config.masking.hash_key = Some(key);
+        }
+        // One key per deployment: policies without their own use the masking section's
+        for policy in config.policies.definitions.values_mut() {
+            if policy.hash_key.is_none() {
+                policy.hash_key = config.masking.hash_key.clone();
+            }
         }
 
         // Validate config
         config.validate()?;
+        config.load_lists()?;
 
         Ok(config)
     }
 
+    /// Reads each policy's `name_list_path`, `deny_list_path` and `terms`.
+    fn load_lists(&mut self) -> Result<(), ConfigError> {
+        let policies = std::iter::once(&mut self.masking).chain(self.policies.definitions.values_mut());
+        for masking in policies {
+            if let Some(path) = &masking.name_list_path {
+                let dictionary = NameDictionary::load(Path::new(path))
+                    .map_err(|e| ConfigError::FileNotFound(format!("{}: {}", path, e)))?;
+                masking.name_dictionary = Arc::new(dictionary);
+            }
+            if let Some(path) = &masking.deny_list_path {
+                let deny_list = DenyList::load(Path::new(path))
+                    .map_err(|e| ConfigError::FileNotFound(format!("{}: {}", path, e)))?;
+                masking.deny_list = Arc::new(deny_list);
+            }
+            if let Some(terms) = &masking.terms {
+                let term_list = TermList::new(terms.clone()).map_err(|e| {
+                    ConfigError::FileNotFound(format!("{}: {}", terms.path.as_deref().unwrap_or("terms"), e))
+                })?;
+                masking.term_list = Some(Arc::new(term_list));
+            }
+        }
+        Ok(())
+    }
+
     fn validate(&self) -> Result<(), ConfigError> {
         // Validate port
         if self.server.port == 0 {
@@ -116,6 +699,34 @@ impl AppConfig {
             ));
         }
 
+        // Validate upstream TLS
+        if let Some(tls) = &self.target.tls {
+            if !self.target.url.starts_with("https://") {
+                return Err(ConfigError::InvalidConfig(
+                    "target.tls requires an https:// target URL".to_string(),
+                ));
+            }
+            if tls.client_cert_path.is_some() != tls.client_key_path.is_some() {
+                return Err(ConfigError::InvalidConfig(
+                    "target.tls client_cert_path and client_key_path must be set together".to_string(),
+                ));
+            }
+            if let Some(version) = &tls.min_version
+                && version != "1.2"
+                && version != "1.3"
+            {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "target.tls.min_version must be \"1.2\" or \"1.3\": {}",
+                    version
+                )));
+            }
+            if !tls.use_builtin_roots && tls.ca_path.is_none() && !tls.insecure_skip_verify {
+                return Err(ConfigError::InvalidConfig(
+                    "target.tls.use_builtin_roots is false but no ca_path is set".to_string(),
+                ));
+            }
+        }
+
         // Validate max_depth
         if self.masking.max_depth == 0 {
             return Err(ConfigError::InvalidConfig(
@@ -123,6 +734,134 @@ impl AppConfig {
             ));
         }
 
+        // Validate limits
+        let limits = &self.limits;
+        if limits.requests_per_sec.is_some_and(|r| r <= 0.0) || limits.burst.is_some_and(|b| b < 1.0) {
+            return Err(ConfigError::InvalidConfig(
+                "limits.requests_per_sec must be > 0 and limits.burst >= 1".to_string(),
+            ));
+        }
+        if limits.max_in_flight == Some(0) || limits.max_in_flight_per_client == Some(0) {
+            return Err(ConfigError::InvalidConfig(
+                "limits.max_in_flight values must be greater than 0".to_string(),
+            ));
+        }
+
+        // Validate size limits
+        if self.server.max_body_bytes == 0 {
+            return Err(ConfigError::InvalidConfig(
+                "server.max_body_bytes must be greater than 0".to_string(),
+            ));
+        }
+        if self.masking.max_line_bytes < MIN_LINE_BYTES {
+            return Err(ConfigError::InvalidConfig(format!(
+                "masking.max_line_bytes must be at least {}",
+                MIN_LINE_BYTES
+            )));
+        }
+        if self.masking.max_string_bytes == 0 {
+            return Err(ConfigError::InvalidConfig(
+                "masking.max_string_bytes must be greater than 0".to_string(),
+            ));
+        }
+        self.masking
+            .network_prefix
+            .check()
+            .map_err(|msg| ConfigError::InvalidConfig(format!("masking.{}", msg)))?;
+        self.masking
+            .scoring
+            .check()
+            .map_err(|msg| ConfigError::InvalidConfig(format!("masking.{}", msg)))?;
+        if self.masking.age_band_years == 0 {
+            return Err(ConfigError::InvalidConfig("masking.age_band_years must be greater than 0".to_string()));
+        }
+        self.masking
+            .check_hash_key()
+            .map_err(|msg| ConfigError::InvalidConfig(format!("masking.{}", msg)))?;
+        if let Some(terms) = &self.masking.terms {
+            terms.check().map_err(|msg| ConfigError::InvalidConfig(format!("masking.{}", msg)))?;
+        }
+
+        // Validate named policies and every reference to them
+        for (name, policy) in &self.policies.definitions {
+            if policy.max_depth == 0 {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "Max depth of policy '{}' must be greater than 0",
+                    name
+                )));
+            }
+            if policy.max_line_bytes < MIN_LINE_BYTES {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "max_line_bytes of policy '{}' must be at least {}",
+                    name, MIN_LINE_BYTES
+                )));
+            }
+            if policy.max_string_bytes == 0 {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "max_string_bytes of policy '{}' must be greater than 0",
+                    name
+                )));
+            }
+            policy
+                .network_prefix
+                .check()
+                .map_err(|msg| ConfigError::InvalidConfig(format!("policy '{}': {}", name, msg)))?;
+            policy
+                .scoring
+                .check()
+                .map_err(|msg| ConfigError::InvalidConfig(format!("policy '{}': {}", name, msg)))?;
+            policy
+                .check_hash_key()
+                .map_err(|msg| ConfigError::InvalidConfig(format!("policy '{}': {}", name, msg)))?;
+            if policy.age_band_years == 0 {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "policy '{}': age_band_years must be greater than 0",
+                    name
+                )));
+            }
+            if let Some(terms) = &policy.terms {
+                terms
+                    .check()
+                    .map_err(|msg| ConfigError::InvalidConfig(format!("policy '{}': {}", name, msg)))?;
+            }
+        }
+        let references = self
+            .policies
+            .default
+            .iter()
+            .chain(self.policies.api_keys.values())
+            .chain(self.policies.identities.values())
+            .chain(self.policies.header_policies.values().flatten());
+        for name in references {
+            if !self.policies.definitions.contains_key(name) {
+                return Err(ConfigError::InvalidConfig(format!(
+                    "Unknown policy referenced: {}",
+                    name
+                )));
+            }
+        }
+
+        // Validate inbound TLS
+        if let Some(tls) = &self.server.tls {
+            if tls.cert_path.is_empty() || tls.key_path.is_empty() {
+                return Err(ConfigError::InvalidConfig(
+                    "server.tls requires both cert_path and key_path".to_string(),
+                ));
+            }
+            if tls.reload_interval_secs == 0 {
+                return Err(ConfigError::InvalidConfig(
+                    "server.tls.reload_interval_secs must be greater than 0".to_string(),
+                ));
+            }
+            if let Some(client_auth) = &tls.client_auth
+                && client_auth.ca_path.is_empty()
+            {
+                return Err(ConfigError::InvalidConfig(
+                    "server.tls.client_auth requires ca_path".to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -137,15 +876,21 @@ mod tests {
             server: ServerConfig {
                 port: 0,
                 host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
             },
             target: TargetConfig {
                 url: "http://localhost:8080".to_string(),
                 timeout_ms: 5000,
+                tls: None,
             },
             masking: MaskingConfig {
                 exclude_fields: vec![],
                 max_depth: 20,
+                ..Default::default()
             },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
         };
 
         assert!(config.validate().is_err());
@@ -157,15 +902,21 @@ mod tests {
             server: ServerConfig {
                 port: 3000,
                 host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
             },
             target: TargetConfig {
                 url: "".to_string(),
                 timeout_ms: 5000,
+                tls: None,
             },
             masking: MaskingConfig {
                 exclude_fields: vec![],
                 max_depth: 20,
+                ..Default::default()
             },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
         };
 
         assert!(config.validate().is_err());
@@ -177,15 +928,21 @@ mod tests {
             server: ServerConfig {
                 port: 3000,
                 host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
             },
             target: TargetConfig {
                 url: "localhost:8080".to_string(),
                 timeout_ms: 5000,
+                tls: None,
             },
             masking: MaskingConfig {
                 exclude_fields: vec![],
                 max_depth: 20,
+                ..Default::default()
             },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
         };
 
         assert!(config.validate().is_err());
@@ -197,15 +954,21 @@ mod tests {
             server: ServerConfig {
                 port: 3000,
                 host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
             },
             target: TargetConfig {
                 url: "http://localhost:8080".to_string(),
                 timeout_ms: 0,
+                tls: None,
             },
             masking: MaskingConfig {
                 exclude_fields: vec![],
                 max_depth: 20,
+                ..Default::default()
             },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
         };
 
         assert!(config.validate().is_err());
@@ -217,15 +980,21 @@ mod tests {
             server: ServerConfig {
                 port: 3000,
                 host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
             },
             target: TargetConfig {
                 url: "http://localhost:8080".to_string(),
                 timeout_ms: 5000,
+                tls: None,
             },
             masking: MaskingConfig {
                 exclude_fields: vec![],
                 max_depth: 0,
+                ..Default::default()
             },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
         };
 
         assert!(config.validate().is_err());
@@ -237,17 +1006,139 @@ mod tests {
             server: ServerConfig {
                 port: 3000,
                 host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
             },
             target: TargetConfig {
                 url: "http://localhost:8080".to_string(),
                 timeout_ms: 5000,
+                tls: None,
             },
             masking: MaskingConfig {
                 exclude_fields: vec![],
                 max_depth: 20,
+                ..Default::default()
             },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
         };
 
         assert!(config.validate().is_ok());
     }
+
+    #[test]
+    fn test_validate_tls_missing_key_path() {
+        let config = AppConfig {
+            server: ServerConfig {
+                port: 3000,
+                host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: Some(ServerTlsConfig {
+                    cert_path: "certs/server.crt".to_string(),
+                    key_path: "".to_string(),
+                    reload_interval_secs: 30,
+                    client_auth: None,
+                }),
+            },
+            target: TargetConfig {
+                url: "http://localhost:8080".to_string(),
+                timeout_ms: 5000,
+                tls: None,
+            },
+            masking: MaskingConfig {
+                exclude_fields: vec![],
+                max_depth: 20,
+                ..Default::default()
+            },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_upstream_tls_requires_https() {
+        let config = AppConfig {
+            server: ServerConfig {
+                port: 3000,
+                host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
+            },
+            target: TargetConfig {
+                url: "http://localhost:8080".to_string(),
+                timeout_ms: 5000,
+                tls: Some(UpstreamTlsConfig {
+                    min_version: Some("1.2".to_string()),
+                    use_builtin_roots: true,
+                    ..Default::default()
+                }),
+            },
+            masking: MaskingConfig {
+                exclude_fields: vec![],
+                max_depth: 20,
+                ..Default::default()
+            },
+            policies: PoliciesConfig::default(),
+            limits: LimitsConfig::default(),
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_validate_unknown_policy_reference() {
+        let mut policies = PoliciesConfig::default();
+        policies.api_keys.insert("k-card".to_string(), "card".to_string());
+
+        let config = AppConfig {
+            server: ServerConfig {
+                port: 3000,
+                host: "0.0.0.0".to_string(),
+                max_body_bytes: default_max_body_bytes(),
+                tls: None,
+            },
+            target: TargetConfig {
+                url: "http://localhost:8080".to_string(),
+                timeout_ms: 5000,
+                tls: None,
+            },
+            masking: MaskingConfig::default(),
+            policies,
+            limits: LimitsConfig::default(),
+        };
+
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_parse_named_policy_with_defaults() {
+        let yaml = r#"
+strategies:
+  credit_card: keep_last4
+detectors:
+  email: false
+"#;
+        let policy: MaskingConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(policy.max_depth, 20);
+        assert_eq!(policy.strategies.credit_card, MaskStrategy::KeepLast4);
+        assert_eq!(policy.strategies.name, MaskStrategy::Partial);
+        assert!(!policy.detectors.email);
+        assert!(policy.detectors.phone);
+    }
+
+    #[test]
+    fn test_hash_rules_need_hash_key() {
+        let yaml = r#"
+field_rules:
+  - { path: "**.card_ref", action: hash }
+"#;
+        let mut policy: MaskingConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(policy.check_hash_key().is_err());
+        policy.hash_key = Some("short".to_string());
+        assert!(policy.check_hash_key().is_err());
+        policy.hash_key = Some("a-long-deployment-secret".to_string());
+        assert!(policy.check_hash_key().is_ok());
+    }
 }
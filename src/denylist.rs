@@ -0,0 +1,200 @@
+use crate::names::is_thai_mark;
+use lazy_static::lazy_static;
+use regex::Regex;
+use ring::digest;
+use std::ops::Range;
+use std::path::Path;
+use std::{fs, io};
+
+lazy_static! {
+    // Identifier-like tokens: CUST-000123, a.b@c.co, or a run of Thai script
+    static ref RE_TOKEN: Regex =
+        Regex::new(r"[A-Za-z0-9](?:[A-Za-z0-9._@+-]*[A-Za-z0-9])?|[\x{0E00}-\x{0E7F}]+").unwrap();
+}
+
+type Sha256 = [u8; 32];
+
+fn sha256(value: &str) -> Sha256 {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(digest::digest(&digest::SHA256, value.as_bytes()).as_ref());
+    hash
+}
+
+fn is_thai(c: char) -> bool {
+    ('\u{0E00}'..='\u{0E7F}').contains(&c)
+}
+
+/// Thai leading vowels (เ แ โ ใ ไ), written before the consonant they belong to.
+fn is_thai_leading_vowel(c: char) -> bool {
+    matches!(c, '\u{0E40}'..='\u{0E44}')
+}
+
+/// Known sensitive values, kept only as SHA-256 hashes, that are masked wherever they appear.
+#[derive(Debug, Clone, Default)]
+pub struct DenyList {
+    /// Sorted for binary search
+    hashes: Vec<Sha256>,
+    /// Lengths in characters of the Thai values, sorted: Thai has no spaces between words, so a
+    /// run of Thai script is searched for substrings of these lengths.
+    thai_lengths: Vec<usize>,
+}
+
+impl DenyList {
+    /// One lowercase or uppercase hex SHA-256 per line, optionally followed by the value's length
+    /// in characters (needed for Thai values, which are found inside longer runs of Thai text);
+    /// blank lines and `#` comments are skipped.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        Self::from_hex_list(&fs::read_to_string(path)?)
+            .map_err(|line| io::Error::new(io::ErrorKind::InvalidData, format!("not a SHA-256 hex digest: {}", line)))
+    }
+
+    fn from_hex_list(list: &str) -> Result<Self, String> {
+        let mut hashes = Vec::new();
+        let mut thai_lengths = Vec::new();
+        for line in list.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (hex, length) = match line.split_once(char::is_whitespace) {
+                Some((hex, length)) => (hex, Some(length.trim())),
+                None => (line, None),
+            };
+            let mut hash = [0; 32];
+            if hex.len() != 64 || !hex.is_ascii() {
+                return Err(line.to_string());
+            }
+            for (i, byte) in hash.iter_mut().enumerate() {
+                *byte = u8::from_str_radix(&hex[i * 2..i * 2 + 2], 16).map_err(|_| line.to_string())?;
+            }
+            if let Some(length) = length {
+                thai_lengths.push(length.parse::<usize>().map_err(|_| line.to_string())?);
+            }
+            hashes.push(hash);
+        }
+        Ok(Self::sorted(hashes, thai_lengths))
+    }
+
+    fn sorted(mut hashes: Vec<Sha256>, mut thai_lengths: Vec<usize>) -> Self {
+        hashes.sort_unstable();
+        hashes.dedup();
+        thai_lengths.retain(|&length| length > 0);
+        thai_lengths.sort_unstable();
+        thai_lengths.dedup();
+        DenyList { hashes, thai_lengths }
+    }
+
+    /// A list of the given values, hashed.
+    pub fn from_values<'a>(values: impl IntoIterator<Item = &'a str>) -> Self {
+        let values: Vec<&str> = values.into_iter().collect();
+        let thai_lengths = values
+            .iter()
+            .filter(|value| value.chars().any(is_thai))
+            .map(|value| value.chars().count())
+            .collect();
+        Self::sorted(values.into_iter().map(sha256).collect(), thai_lengths)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    pub fn contains(&self, value: &str) -> bool {
+        self.hashes.binary_search(&sha256(value)).is_ok()
+    }
+
+    /// Tokens of `text` (or the parts of a token between `.`, `@`, `+`, `-`, `_`) on the list.
+    pub fn find(&self, text: &str) -> Vec<Range<usize>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let mut found = Vec::new();
+        for token in RE_TOKEN.find_iter(text) {
+            if self.contains(token.as_str()) {
+                found.push(token.range());
+                continue;
+            }
+            if token.as_str().starts_with(is_thai) {
+                let offset = token.start();
+                found.extend(self.find_in_thai_run(token.as_str()).into_iter().map(|r| r.start + offset..r.end + offset));
+                continue;
+            }
+            let mut start = token.start();
+            for part in token.as_str().split(['.', '@', '+', '-', '_']) {
+                if !part.is_empty() && part.len() < token.len() && self.contains(part) {
+                    found.push(start..start + part.len());
+                }
+                start += part.len() + 1;
+            }
+        }
+        found
+    }
+
+    /// Listed values inside a run of Thai script, at the lengths on the list, longest first at each
+    /// position; a match never starts after a leading vowel or ends before a vowel or tone mark.
+    fn find_in_thai_run(&self, run: &str) -> Vec<Range<usize>> {
+        let mut found = Vec::new();
+        if self.thai_lengths.is_empty() {
+            return found;
+        }
+        let chars: Vec<(usize, char)> = run.char_indices().collect();
+        let byte_at = |i: usize| chars.get(i).map_or(run.len(), |&(at, _)| at);
+        let mut start = 0;
+        while start < chars.len() {
+            let starts_cluster =
+                !is_thai_mark(chars[start].1) && (start == 0 || !is_thai_leading_vowel(chars[start - 1].1));
+            let matched = starts_cluster
+                .then(|| {
+                    self.thai_lengths.iter().rev().find(|&&length| {
+                        let end = start + length;
+                        end <= chars.len()
+                            && !chars.get(end).is_some_and(|&(_, c)| is_thai_mark(c))
+                            && self.contains(&run[byte_at(start)..byte_at(end)])
+                    })
+                })
+                .flatten();
+            match matched {
+                Some(&length) => {
+                    found.push(byte_at(start)..byte_at(start + length));
+                    start += length;
+                }
+                None => start += 1,
+            }
+        }
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_load_hex_list() {
+        let hex = "# customer ids\n\n9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08\n";
+        let list = DenyList::from_hex_list(hex).unwrap();
+        assert!(list.contains("test"));
+        assert!(!list.contains("Test"));
+        assert!(DenyList::from_hex_list("9f86d0").is_err());
+    }
+
+    #[test]
+    fn test_find_exact_tokens() {
+        let list = DenyList::from_values(["CUST000123", "สมชาย"]);
+        let text = "ref CUST000123, ref-CUST000123-b CUST0001234 ลูกค้า สมชาย";
+        let found: Vec<&str> = list.find(text).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(found, vec!["CUST000123", "CUST000123", "สมชาย"]);
+    }
+
+    #[test]
+    fn test_find_inside_thai_runs() {
+        let list = DenyList::from_values(["สมชาย"]);
+        let text = "ลูกค้าสมชายโทรมา สมชายา";
+        let found: Vec<&str> = list.find(text).into_iter().map(|r| &text[r]).collect();
+        assert_eq!(found, vec!["สมชาย"]);
+
+        // A hex list finds Thai values inside a run only when the line gives the length
+        let hex = "70a003b381e338190fc3a79a18a6247a446a9a1b0456ea027a5dc5408a901d91 5";
+        let hex_list = DenyList::from_hex_list(hex).unwrap();
+        assert_eq!(hex_list.find("ลูกค้าสมชายโทรมา").len(), 1);
+    }
+}
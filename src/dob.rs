@@ -0,0 +1,222 @@
+use crate::normalize::context_before;
+use crate::validator;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Month names and abbreviations, Thai and English.
+const MONTHS: &[(&str, u32)] = &[
+    ("มกราคม", 1), ("กุมภาพันธ์", 2), ("มีนาคม", 3), ("เมษายน", 4), ("พฤษภาคม", 5), ("มิถุนายน", 6),
+    ("กรกฎาคม", 7), ("สิงหาคม", 8), ("กันยายน", 9), ("ตุลาคม", 10), ("พฤศจิกายน", 11), ("ธันวาคม", 12),
+    ("ม.ค.", 1), ("ก.พ.", 2), ("มี.ค.", 3), ("เม.ย.", 4), ("พ.ค.", 5), ("มิ.ย.", 6),
+    ("ก.ค.", 7), ("ส.ค.", 8), ("ก.ย.", 9), ("ต.ค.", 10), ("พ.ย.", 11), ("ธ.ค.", 12),
+    ("january", 1), ("february", 2), ("march", 3), ("april", 4), ("may", 5), ("june", 6),
+    ("july", 7), ("august", 8), ("september", 9), ("october", 10), ("november", 11), ("december", 12),
+    ("jan", 1), ("feb", 2), ("mar", 3), ("apr", 4), ("jun", 6), ("jul", 7), ("aug", 8), ("sep", 9),
+    ("sept", 9), ("oct", 10), ("nov", 11), ("dec", 12),
+];
+
+/// Years from here on are Buddhist Era (CE + 543).
+const FIRST_BE_YEAR: u32 = 2400;
+
+lazy_static! {
+    // Day first: 12/05/2530, 12-05-1987, 12.5.1987
+    static ref RE_DMY: Regex = Regex::new(r"([0-9]{1,2})[/.-]([0-9]{1,2})[/.-]([0-9]{4})").unwrap();
+
+    // ISO: 1987-05-12
+    static ref RE_ISO: Regex = Regex::new(r"([0-9]{4})-([0-9]{2})-([0-9]{2})").unwrap();
+
+    // Month by name: 12 พฤษภาคม 2530, 12 พ.ค. พ.ศ. 2530, 12 May 1987
+    static ref RE_NAMED: Regex = {
+        let mut names: Vec<&str> = MONTHS.iter().map(|(name, _)| *name).collect();
+        names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+        let names: Vec<String> = names.into_iter().map(regex::escape).collect();
+        Regex::new(&format!(r"(?i)([0-9]{{1,2}}) ?({}) ?(?:พ\.ศ\. ?|ค\.ศ\. ?)?([0-9]{{4}})", names.join("|"))).unwrap()
+    };
+
+    static ref RE_DOB_CONTEXT: Regex =
+        Regex::new(r"(?i)(\bdob\b|d\.o\.b|birth|วันเกิด|เกิดวันที่|เกิดเมื่อ|ปีเกิด)").unwrap();
+}
+
+/// A calendar date, year in CE.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct Date {
+    pub year: u32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    pub fn today() -> Self {
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Self::from_days((secs / 86_400) as i64)
+    }
+
+    /// Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    fn from_days(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as u32;
+        Date { year, month, day }
+    }
+}
+
+#[derive(Clone, Copy)]
+enum DateFormat {
+    Iso,
+    DayFirst,
+    MonthName,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DateMatch {
+    pub start: usize,
+    pub end: usize,
+    pub birth: Date,
+    /// Where the year is, as written (2530 stays BE)
+    pub year: Range<usize>,
+    /// Where the month name is, for dates written with one
+    pub month_name: Option<Range<usize>>,
+}
+
+fn parse(year: &str, month: u32, day: &str, today: Date) -> Option<Date> {
+    let year: u32 = year.parse().ok()?;
+    let year = if year >= FIRST_BE_YEAR { year - 543 } else { year };
+    let date = Date {
+        year,
+        month,
+        day: day.parse().ok()?,
+    };
+    (year >= 1900 && validator::is_valid_date(year, month, date.day) && date <= today).then_some(date)
+}
+
+/// Birth dates: day-first, ISO or month-name dates in CE or BE that could be a birth date.
+/// With `needs_keyword`, only those after dob/birth/วันเกิด.
+pub fn find_dates(text: &str, needs_keyword: bool) -> Vec<DateMatch> {
+    if needs_keyword && !RE_DOB_CONTEXT.is_match(text) {
+        return Vec::new();
+    }
+    let today = Date::today();
+    let bytes = text.as_bytes();
+    let mut found: Vec<DateMatch> = Vec::new();
+
+    let formats: [(DateFormat, &Regex); 3] = [
+        (DateFormat::Iso, &RE_ISO),
+        (DateFormat::DayFirst, &RE_DMY),
+        (DateFormat::MonthName, &RE_NAMED),
+    ];
+    for (format, re) in formats {
+        for caps in re.captures_iter(text) {
+            let mat = caps.get(0).unwrap();
+            let glued = mat.start() > 0 && bytes[mat.start() - 1].is_ascii_digit()
+                || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_digit());
+            if glued
+                || found.iter().any(|f| f.start < mat.end() && mat.start() < f.end)
+                || needs_keyword && !RE_DOB_CONTEXT.is_match(context_before(text, mat.start()))
+            {
+                continue;
+            }
+
+            let (day, month, year, month_name) = match format {
+                DateFormat::Iso => (&caps[3], caps[2].parse().ok(), caps.get(1).unwrap(), None),
+                DateFormat::DayFirst => (&caps[1], caps[2].parse().ok(), caps.get(3).unwrap(), None),
+                DateFormat::MonthName => {
+                    let name = caps.get(2).unwrap();
+                    let month = MONTHS.iter().find(|(m, _)| m.eq_ignore_ascii_case(name.as_str())).map(|(_, n)| *n);
+                    (&caps[1], month, caps.get(3).unwrap(), Some(name.range()))
+                }
+            };
+            if let Some(birth) = month.and_then(|month| parse(year.as_str(), month, day, today)) {
+                found.push(DateMatch {
+                    start: mat.start(),
+                    end: mat.end(),
+                    birth,
+                    year: year.range(),
+                    month_name,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|m| m.start);
+    found
+}
+
+/// Day and month hidden, year kept as written: 12/05/2530 -> XX/XX/2530, 12 May 1987 -> XX XX 1987.
+pub fn mask_day_month(text: &str, found: &DateMatch) -> String {
+    let mut masked = String::new();
+    let mut at = found.start;
+    if let Some(name) = &found.month_name {
+        masked.extend(text[at..name.start].chars().map(|c| if c.is_ascii_digit() { 'X' } else { c }));
+        masked.push_str("XX");
+        at = name.end;
+    }
+    for (i, c) in text[at..found.end].char_indices() {
+        let in_year = found.year.contains(&(at + i));
+        masked.push(if c.is_ascii_digit() && !in_year { 'X' } else { c });
+    }
+    masked
+}
+
+/// Age on `today` as a band `width` years wide: 30-39.
+pub fn age_band(birth: Date, today: Date, width: u8) -> String {
+    let width = u32::from(width.max(1));
+    let had_birthday = (today.month, today.day) >= (birth.month, birth.day);
+    let age = today.year - birth.year - u32::from(!had_birthday);
+    let lower = age / width * width;
+    format!("{}-{}", lower, lower + width - 1)
+}
+
+/// JSON keys that hold a birth date: `dob`, `date_of_birth`, `birthDate`, ...
+pub fn is_dob_key(key: &str) -> bool {
+    let compact: String = key
+        .chars()
+        .filter(|c| !matches!(c, '_' | '-' | '.' | ' '))
+        .flat_map(char::to_lowercase)
+        .collect();
+    matches!(compact.as_str(), "dob" | "dateofbirth" | "birthdate" | "birthday" | "birthdt" | "วันเกิด")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dates(text: &str) -> Vec<(&str, Date)> {
+        find_dates(text, true)
+            .iter()
+            .map(|m| (&text[m.start..m.end], m.birth))
+            .collect()
+    }
+
+    fn date(year: u32, month: u32, day: u32) -> Date {
+        Date { year, month, day }
+    }
+
+    #[test]
+    fn test_gregorian_and_buddhist_era() {
+        assert_eq!(dates("วันเกิด 12/05/2530"), vec![("12/05/2530", date(1987, 5, 12))]);
+        assert_eq!(dates("dob=1987-05-12"), vec![("1987-05-12", date(1987, 5, 12))]);
+        assert_eq!(dates("เกิดวันที่ 12 พ.ค. 2530"), vec![("12 พ.ค. 2530", date(1987, 5, 12))]);
+        assert_eq!(dates("Date of birth: 3 March 1990"), vec![("3 March 1990", date(1990, 3, 3))]);
+        // No keyword, impossible date, future date
+        assert!(dates("paid 12/05/2530").is_empty());
+        assert!(dates("dob 31/02/1990").is_empty());
+        assert!(dates("dob 01/01/2999").is_empty());
+    }
+
+    #[test]
+    fn test_generalisation() {
+        let text = "dob 12 พฤษภาคม 2530";
+        let found = find_dates(text, true);
+        assert_eq!(mask_day_month(text, &found[0]), "XX XX 2530");
+        assert_eq!(age_band(date(1987, 5, 12), date(2026, 5, 11), 10), "30-39");
+        assert_eq!(age_band(date(1987, 5, 12), date(2027, 5, 12), 10), "40-49");
+        assert_eq!(Date::from_days(20_000), date(2024, 10, 4));
+    }
+}
@@ -0,0 +1,129 @@
+use crate::normalize::context_before;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Thai passport: 1-2 letters + 7 digits, e.g. AA1234567
+    static ref RE_PASSPORT: Regex = Regex::new(r"[A-Z]{1,2}[0-9]{7}").unwrap();
+    static ref RE_PASSPORT_CONTEXT: Regex =
+        Regex::new(r"(?i)(passport|หนังสือเดินทาง|พาสปอร์ต)").unwrap();
+
+    // Driver's license: 8 digits, written as 12345678 or 12 345678
+    static ref RE_DRIVER_LICENSE: Regex = Regex::new(r"[0-9]{2}[ -]?[0-9]{6}").unwrap();
+    static ref RE_DRIVER_LICENSE_CONTEXT: Regex =
+        Regex::new(r"(?i)(driv(?:er|ing)'?s? ?licen[cs]e|ใบขับขี่|ใบอนุญาตขับรถ)").unwrap();
+
+    // Vehicle plate: optional digit, 1-2 consonants, 1-4 digits, e.g. กข 1234, 1กข 234
+    static ref RE_VEHICLE_PLATE: Regex = Regex::new(r"[0-9]?[ก-ฮ]{1,2} ?-?[0-9]{1,4}").unwrap();
+    static ref RE_VEHICLE_PLATE_CONTEXT: Regex =
+        Regex::new(r"(?i)(ทะเบียน|license plate|plate|registration)").unwrap();
+
+    // Social security number: 13 digits, plain or in the national ID layout
+    static ref RE_SOCIAL_SECURITY: Regex =
+        Regex::new(r"[0-9]{13}|[0-9]-[0-9]{4}-[0-9]{5}-[0-9]{2}-[0-9]").unwrap();
+    static ref RE_SOCIAL_SECURITY_CONTEXT: Regex =
+        Regex::new(r"(?i)(ประกันสังคม|social security|\bsso\b|\bssn\b)").unwrap();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum DocumentKind {
+    Passport,
+    DriverLicense,
+    VehiclePlate,
+    SocialSecurity,
+}
+
+impl DocumentKind {
+    /// Detector that finds this kind, as named in the config.
+    pub fn detector(self) -> &'static str {
+        match self {
+            DocumentKind::Passport => "passport",
+            DocumentKind::DriverLicense => "driver_license",
+            DocumentKind::VehiclePlate => "vehicle_plate",
+            DocumentKind::SocialSecurity => "social_security",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct DocumentMatch {
+    pub start: usize,
+    pub end: usize,
+    pub kind: DocumentKind,
+}
+
+/// Which document detectors are on.
+pub struct DocumentToggles {
+    pub passport: bool,
+    pub driver_license: bool,
+    pub vehicle_plate: bool,
+    pub social_security: bool,
+}
+
+/// Finds document numbers that follow their context keyword, earliest first and never overlapping.
+pub fn find_documents(text: &str, toggles: &DocumentToggles) -> Vec<DocumentMatch> {
+    let rules: [(bool, DocumentKind, &Regex, &Regex); 4] = [
+        (toggles.passport, DocumentKind::Passport, &RE_PASSPORT, &RE_PASSPORT_CONTEXT),
+        (toggles.driver_license, DocumentKind::DriverLicense, &RE_DRIVER_LICENSE, &RE_DRIVER_LICENSE_CONTEXT),
+        (toggles.vehicle_plate, DocumentKind::VehiclePlate, &RE_VEHICLE_PLATE, &RE_VEHICLE_PLATE_CONTEXT),
+        (toggles.social_security, DocumentKind::SocialSecurity, &RE_SOCIAL_SECURITY, &RE_SOCIAL_SECURITY_CONTEXT),
+    ];
+
+    let mut found: Vec<DocumentMatch> = Vec::new();
+    for (enabled, kind, value, context) in rules {
+        // Cheap exit for the common case: no keyword anywhere in the text
+        if !enabled || !context.is_match(text) {
+            continue;
+        }
+        for mat in value.find_iter(text) {
+            let glued = text[..mat.start()].chars().next_back().is_some_and(char::is_alphanumeric)
+                || text[mat.end()..].chars().next().is_some_and(char::is_alphanumeric);
+            let overlaps = found.iter().any(|f| f.start < mat.end() && mat.start() < f.end);
+            if !glued && !overlaps && context.is_match(context_before(text, mat.start())) {
+                found.push(DocumentMatch {
+                    start: mat.start(),
+                    end: mat.end(),
+                    kind,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|m| m.start);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: DocumentToggles = DocumentToggles {
+        passport: true,
+        driver_license: true,
+        vehicle_plate: true,
+        social_security: true,
+    };
+
+    fn found(text: &str) -> Vec<(&str, DocumentKind)> {
+        find_documents(text, &ALL)
+            .iter()
+            .map(|m| (&text[m.start..m.end], m.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_documents_after_keywords() {
+        assert_eq!(found("passport no. AA1234567"), vec![("AA1234567", DocumentKind::Passport)]);
+        assert_eq!(found("ใบขับขี่ 12 345678"), vec![("12 345678", DocumentKind::DriverLicense)]);
+        assert_eq!(
+            found("ทะเบียน กข 1234 กรุงเทพมหานคร"),
+            vec![("กข 1234", DocumentKind::VehiclePlate)]
+        );
+        assert_eq!(found("เลขประกันสังคม 3-1234-56789-01-2"), vec![("3-1234-56789-01-2", DocumentKind::SocialSecurity)]);
+    }
+
+    #[test]
+    fn test_no_keyword_no_match() {
+        assert!(found("order AA1234567 shipped 12345678").is_empty());
+        assert!(found("ร้าน กข 1234").is_empty());
+    }
+}
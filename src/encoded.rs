@@ -0,0 +1,138 @@
+use base64::Engine;
+use base64::engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Base64 blob, standard or URL-safe alphabet: eyJlbWFpbCI6ImFAYi5jb20ifQ==
+    static ref RE_BASE64: Regex = Regex::new(r"[A-Za-z0-9+/_-]{16,}={0,2}").unwrap();
+
+    // Run of URL characters holding at least one %XX: john%40test.com
+    static ref RE_PERCENT: Regex = Regex::new(
+        r"(?:[A-Za-z0-9._~+!*'()-]|%[0-9A-Fa-f]{2})*%[0-9A-Fa-f]{2}(?:[A-Za-z0-9._~+!*'()-]|%[0-9A-Fa-f]{2})*"
+    ).unwrap();
+}
+
+/// Decoded bytes worth masking: UTF-8 text without control characters.
+fn as_text(bytes: Vec<u8>) -> Option<String> {
+    let text = String::from_utf8(bytes).ok()?;
+    let printable = !text.is_empty() && text.chars().all(|c| !c.is_control() || matches!(c, '\n' | '\r' | '\t'));
+    printable.then_some(text)
+}
+
+fn base64_engine(blob: &str) -> &'static base64::engine::GeneralPurpose {
+    let url_safe = blob.contains(['-', '_']);
+    let padded = blob.ends_with('=') || blob.len().is_multiple_of(4);
+    match (url_safe, padded) {
+        (false, true) => &STANDARD,
+        (false, false) => &STANDARD_NO_PAD,
+        (true, true) => &URL_SAFE,
+        (true, false) => &URL_SAFE_NO_PAD,
+    }
+}
+
+/// Decodes `%XX` escapes; `None` unless the result is text.
+pub(crate) fn percent_decode(run: &str) -> Option<String> {
+    let bytes = run.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            decoded.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+    as_text(decoded)
+}
+
+/// Percent-encodes `text`, leaving unreserved characters and those `original` had unescaped.
+pub(crate) fn percent_encode(text: &str, original: &str) -> String {
+    let mut encoded = String::with_capacity(text.len() * 3);
+    for c in text.chars() {
+        let literal = c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~') || original.contains(c);
+        if literal && c != '%' {
+            encoded.push(c);
+        } else {
+            let mut buf = [0; 4];
+            for b in c.encode_utf8(&mut buf).bytes() {
+                encoded.push_str(&format!("%{:02X}", b));
+            }
+        }
+    }
+    encoded
+}
+
+/// Replaces each match of `re` in `text` for which `rewrite` returns something.
+fn rewrite_matches(text: &str, re: &Regex, mut rewrite: impl FnMut(&str) -> Option<String>) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for mat in re.find_iter(text) {
+        if let Some(replacement) = rewrite(mat.as_str()) {
+            result.push_str(&text[last..mat.start()]);
+            result.push_str(&replacement);
+            last = mat.end();
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+/// Finds base64 blobs and percent-encoded runs up to `max_bytes` long, hands their decoded text to
+/// `mask`, and re-encodes whatever it changed the way it was encoded.
+pub fn mask_encoded(text: &str, max_bytes: usize, mut mask: impl FnMut(&str) -> String) -> String {
+    let mut changed = |decoded: &str| Some(mask(decoded)).filter(|masked| masked != decoded);
+
+    let text = rewrite_matches(text, &RE_BASE64, |blob| {
+        if blob.len() > max_bytes {
+            return None;
+        }
+        let engine = base64_engine(blob);
+        let decoded = as_text(engine.decode(blob).ok()?)?;
+        changed(&decoded).map(|masked| engine.encode(masked))
+    });
+
+    if !text.contains('%') {
+        return text;
+    }
+    rewrite_matches(&text, &RE_PERCENT, |run| {
+        if run.len() > max_bytes {
+            return None;
+        }
+        let decoded = percent_decode(run)?;
+        changed(&decoded).map(|masked| percent_encode(&masked, run))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upper_digits(text: &str) -> String {
+        text.replace(|c: char| c.is_ascii_digit(), "X")
+    }
+
+    #[test]
+    fn test_base64_round_trip() {
+        let blob = STANDARD.encode("tel 0812345678");
+        let masked = mask_encoded(&format!("payload={}", blob), 1024, upper_digits);
+        assert_eq!(masked, format!("payload={}", STANDARD.encode("tel XXXXXXXXXX")));
+
+        let blob = URL_SAFE_NO_PAD.encode("id 1103700012346 ok");
+        let masked = mask_encoded(&blob, 1024, upper_digits);
+        assert_eq!(URL_SAFE_NO_PAD.decode(masked).unwrap(), b"id XXXXXXXXXXXXX ok");
+
+        // Not text once decoded, or too long: left alone
+        assert_eq!(mask_encoded("AAAAAAAAAAAAAAAAAAAA", 1024, upper_digits), "AAAAAAAAAAAAAAAAAAAA");
+        assert_eq!(mask_encoded(&blob, 8, upper_digits), blob);
+    }
+
+    #[test]
+    fn test_percent_encoding_round_trip() {
+        let masked = mask_encoded("to=john%40test.com&x=1", 1024, |t| t.replace("john", "jo**"));
+        assert_eq!(masked, "to=jo%2A%2A%40test.com&x=1");
+    }
+}
@@ -0,0 +1,198 @@
+use crate::config::MaskStrategy;
+use serde::Deserialize;
+
+/// What a field rule does with the value it selects.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldAction {
+    /// Replace the value (and anything under it) with [REDACTED]
+    Redact,
+    /// Replace the value with its HMAC-SHA256 under `hash_key`, so it can still be joined on
+    Hash,
+    /// Leave the value and everything under it as it is
+    Keep,
+    /// Mask with `strategy` (partial keeps the first 2 characters)
+    Mask,
+}
+
+/// One step of a JSON path: an object key or an array index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStep<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    /// Object key, `*` and `?` allowed; `*` alone also matches any array index
+    Key(String),
+    Index(usize),
+    AnyIndex,
+    /// `**`: zero or more steps
+    Descend,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Selector {
+    /// `$.customer.*.email`, `**.password`
+    Path(Vec<Segment>),
+    /// Glob on the field's own key: `*_nm`
+    Key(String),
+}
+
+/// `path` (JSON path from `$` or `**`) or `key` (glob on the key alone) -> action.
+#[derive(Debug, Deserialize, Clone, PartialEq, Eq)]
+#[serde(try_from = "FieldRuleConfig")]
+pub struct FieldRule {
+    selector: Selector,
+    pub action: FieldAction,
+    pub strategy: MaskStrategy,
+}
+
+#[derive(Deserialize)]
+struct FieldRuleConfig {
+    path: Option<String>,
+    key: Option<String>,
+    action: FieldAction,
+    #[serde(default)]
+    strategy: MaskStrategy,
+}
+
+impl TryFrom<FieldRuleConfig> for FieldRule {
+    type Error = String;
+
+    fn try_from(config: FieldRuleConfig) -> Result<Self, String> {
+        let selector = match (config.path, config.key) {
+            (Some(path), None) => Selector::Path(parse_path(&path)?),
+            (None, Some(key)) => Selector::Key(key),
+            _ => return Err("field rule needs exactly one of `path` or `key`".to_string()),
+        };
+        Ok(FieldRule {
+            selector,
+            action: config.action,
+            strategy: config.strategy,
+        })
+    }
+}
+
+/// `$.a.*.b`, `$.items[0].sku`, `$.items[*].sku`, `**.password`
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let rest = if let Some(rest) = path.strip_prefix('$') {
+        rest.strip_prefix('.').unwrap_or(rest)
+    } else if path.starts_with("**") {
+        path
+    } else {
+        return Err(format!("field rule path '{}' must start with `$` or `**`", path));
+    };
+
+    let mut segments = Vec::new();
+    for part in rest.split('.').filter(|p| !p.is_empty()) {
+        let (name, indexes) = part.split_once('[').map_or((part, ""), |(name, idx)| (name, idx));
+        match name {
+            "" => {}
+            "**" => segments.push(Segment::Descend),
+            name => segments.push(Segment::Key(name.to_string())),
+        }
+        for index in indexes.split('[').filter(|i| !i.is_empty()) {
+            let index = index
+                .strip_suffix(']')
+                .ok_or_else(|| format!("field rule path '{}': unclosed `[`", path))?;
+            segments.push(match index {
+                "*" => Segment::AnyIndex,
+                n => Segment::Index(n.parse().map_err(|_| format!("field rule path '{}': bad index '{}'", path, n))?),
+            });
+        }
+    }
+    Ok(segments)
+}
+
+/// Case-insensitive glob: `*` any run of characters, `?` one character.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let text: Vec<char> = text.chars().flat_map(char::to_lowercase).collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, t));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            t = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn segments_match(segments: &[Segment], path: &[PathStep]) -> bool {
+    let Some((first, rest)) = segments.split_first() else {
+        return path.is_empty();
+    };
+    if *first == Segment::Descend {
+        return (0..=path.len()).any(|skip| segments_match(rest, &path[skip..]));
+    }
+    let Some((step, path_rest)) = path.split_first() else {
+        return false;
+    };
+    let step_matches = match (first, step) {
+        (Segment::Key(pattern), PathStep::Key(key)) => glob_match(pattern, key),
+        (Segment::Key(pattern), PathStep::Index(_)) => pattern == "*",
+        (Segment::Index(n), PathStep::Index(i)) => n == i,
+        (Segment::AnyIndex, PathStep::Index(_)) => true,
+        _ => false,
+    };
+    step_matches && segments_match(rest, path_rest)
+}
+
+impl FieldRule {
+    /// Whether this rule selects the value at `path` (root first).
+    pub fn matches(&self, path: &[PathStep]) -> bool {
+        match &self.selector {
+            Selector::Path(segments) => segments_match(segments, path),
+            Selector::Key(pattern) => matches!(path.last(), Some(PathStep::Key(key)) if glob_match(pattern, key)),
+        }
+    }
+}
+
+/// The old default: keys containing "name" or "user" are masked with the name strategy.
+pub fn is_name_key(key: &str) -> bool {
+    let key = key.to_lowercase();
+    key.contains("name") || key.contains("user")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(yaml: &str) -> FieldRule {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_path_selectors() {
+        let path = [PathStep::Key("customer"), PathStep::Index(0), PathStep::Key("email")];
+        assert!(rule("{ path: '$.customer.*.email', action: redact }").matches(&path));
+        assert!(rule("{ path: '$.customer[0].email', action: redact }").matches(&path));
+        assert!(rule("{ path: '**.email', action: redact }").matches(&path));
+        assert!(!rule("{ path: '$.email', action: redact }").matches(&path));
+        assert!(rule("{ path: '**', action: keep }").matches(&path));
+
+        assert!(serde_yaml::from_str::<FieldRule>("{ path: 'customer.email', action: redact }").is_err());
+        assert!(serde_yaml::from_str::<FieldRule>("{ action: redact }").is_err());
+    }
+
+    #[test]
+    fn test_key_globs() {
+        let full_nm = rule("{ key: '*_nm', action: mask, strategy: keep_last4 }");
+        assert!(full_nm.matches(&[PathStep::Key("order"), PathStep::Key("FULL_NM")]));
+        assert!(!full_nm.matches(&[PathStep::Key("full_nm"), PathStep::Index(1)]));
+        assert_eq!(full_nm.strategy, MaskStrategy::KeepLast4);
+        assert!(glob_match("cust?mer*", "Customer_Name"));
+    }
+}
@@ -0,0 +1,127 @@
+use crate::normalize::context_before;
+use crate::validator;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // IBAN: country + check digits, then groups of 4 (printed with spaces or compact)
+    // Example: GB82 WEST 1234 5698 7654 32, DE89370400440532013000
+    static ref RE_IBAN: Regex =
+        Regex::new(r"[A-Z]{2}[0-9]{2}(?: ?[A-Z0-9]{4}){2,7}(?: ?[A-Z0-9]{1,3})?").unwrap();
+
+    // SWIFT/BIC: BKKBTHBK, DEUTDEFF500
+    static ref RE_SWIFT_BIC: Regex = Regex::new(r"[A-Z]{6}[A-Z0-9]{2}(?:[A-Z0-9]{3})?").unwrap();
+
+    // ABA routing number: 9 digits
+    static ref RE_ABA_ROUTING: Regex = Regex::new(r"[0-9]{9}").unwrap();
+
+    // An 8-letter word or a 9-digit number is too common to take without a keyword
+    static ref RE_SWIFT_CONTEXT: Regex = Regex::new(r"(?i)(swift|\bbic\b)").unwrap();
+    static ref RE_ROUTING_CONTEXT: Regex = Regex::new(r"(?i)(routing|\baba\b|\brtn\b)").unwrap();
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum FinancialKind {
+    Iban,
+    SwiftBic,
+    AbaRouting,
+}
+
+impl FinancialKind {
+    /// Detector that finds this kind, as named in the config.
+    pub fn detector(self) -> &'static str {
+        match self {
+            FinancialKind::Iban => "iban",
+            FinancialKind::SwiftBic => "swift_bic",
+            FinancialKind::AbaRouting => "aba_routing",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FinancialMatch {
+    pub start: usize,
+    pub end: usize,
+    pub kind: FinancialKind,
+}
+
+/// Which financial identifier detectors are on.
+pub struct FinancialToggles {
+    pub iban: bool,
+    pub swift_bic: bool,
+    pub aba_routing: bool,
+}
+
+/// Finds IBANs (mod-97), SWIFT/BIC codes and ABA routing numbers, earliest first and never overlapping.
+/// BIC and routing numbers also need a keyword before them.
+pub fn find_financial_ids(text: &str, toggles: &FinancialToggles) -> Vec<FinancialMatch> {
+    let rules: [(bool, FinancialKind, &Regex, Option<&Regex>); 3] = [
+        (toggles.iban, FinancialKind::Iban, &RE_IBAN, None),
+        (toggles.swift_bic, FinancialKind::SwiftBic, &RE_SWIFT_BIC, Some(&RE_SWIFT_CONTEXT)),
+        (toggles.aba_routing, FinancialKind::AbaRouting, &RE_ABA_ROUTING, Some(&RE_ROUTING_CONTEXT)),
+    ];
+
+    let bytes = text.as_bytes();
+    let mut found: Vec<FinancialMatch> = Vec::new();
+    for (enabled, kind, re, context) in rules {
+        if !enabled || context.is_some_and(|c| !c.is_match(text)) {
+            continue;
+        }
+        for mat in re.find_iter(text) {
+            let glued = mat.start() > 0 && bytes[mat.start() - 1].is_ascii_alphanumeric()
+                || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_alphanumeric());
+            if glued || found.iter().any(|f| f.start < mat.end() && mat.start() < f.end) {
+                continue;
+            }
+            let valid = match kind {
+                FinancialKind::Iban => validator::is_iban(mat.as_str()),
+                FinancialKind::SwiftBic => validator::is_swift_bic(mat.as_str()),
+                FinancialKind::AbaRouting => validator::is_aba_routing(mat.as_str()),
+            };
+            if valid && context.is_none_or(|c| c.is_match(context_before(text, mat.start()))) {
+                found.push(FinancialMatch {
+                    start: mat.start(),
+                    end: mat.end(),
+                    kind,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|m| m.start);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: FinancialToggles = FinancialToggles {
+        iban: true,
+        swift_bic: true,
+        aba_routing: true,
+    };
+
+    fn found(text: &str) -> Vec<(&str, FinancialKind)> {
+        find_financial_ids(text, &ALL)
+            .iter()
+            .map(|m| (&text[m.start..m.end], m.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_iban_swift_and_routing() {
+        assert_eq!(
+            found("to GB82 WEST 1234 5698 7654 32 via SWIFT BKKBTHBK"),
+            vec![
+                ("GB82 WEST 1234 5698 7654 32", FinancialKind::Iban),
+                ("BKKBTHBK", FinancialKind::SwiftBic)
+            ]
+        );
+        assert_eq!(found("routing 021000021"), vec![("021000021", FinancialKind::AbaRouting)]);
+    }
+
+    #[test]
+    fn test_lookalikes_need_keywords() {
+        assert!(found("status DATABASE ok, order 021000021").is_empty());
+    }
+}
@@ -1,21 +1,31 @@
 use axum::{
-    extract::State,
+    extract::{ConnectInfo, Path, State},
     response::IntoResponse,
-    http::StatusCode,
+    http::{header, HeaderMap, StatusCode},
     body::Body,
+    Extension,
 };
 use futures_util::StreamExt;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use crate::masker;
 use crate::config::AppConfig;
+use crate::limits::Limiter;
+use crate::policy::{self, PolicyRequest, PolicySet};
+use crate::stream::{StreamError, StreamMasker};
+use crate::tls::ClientIdentity;
 use reqwest::Client;
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 use bytes::Bytes;
 use tokio_stream::wrappers::ReceiverStream;
 
 pub struct AppState {
     pub http_client: Client,
+    /// `target.url`, with the host swapped for the SNI name when one is configured.
+    pub upstream_url: String,
     pub config: AppConfig,
+    pub policies: PolicySet,
+    pub limiter: Limiter,
 }
 
 /// Health check endpoint for Kubernetes/Docker
@@ -26,42 +36,91 @@ pub async fn health_check() -> impl IntoResponse {
 
 pub async fn handle_log(
     State(state): State<Arc<AppState>>,
+    route_policy: Option<Path<String>>,
+    identity: Option<Extension<ClientIdentity>>,
+    peer: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
     body: Body,
 ) -> impl IntoResponse {
+    let header_value = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    // 0. Admission: rate limit and concurrency caps per client (configured API key, mTLS identity, else IP).
+    // Unknown keys count against the peer address, so rotating X-Api-Key values doesn't buy fresh buckets.
+    let api_key = header_value(policy::API_KEY_HEADER).filter(|key| state.config.policies.api_keys.contains_key(*key));
+    let client_key = match (api_key, &identity, &peer) {
+        (Some(key), _, _) => format!("key:{}", key),
+        (None, Some(Extension(ClientIdentity(id))), _) => format!("id:{}", id),
+        (None, None, Some(ConnectInfo(addr))) => format!("ip:{}", addr.ip()),
+        (None, None, None) => "anonymous".to_string(),
+    };
+    let limit_guard = match state.limiter.acquire(&client_key) {
+        Ok(guard) => Arc::new(guard),
+        Err(rejection) => {
+            warn!("Throttled {}: {}", client_key, rejection);
+            return rejection.into_response();
+        }
+    };
+
+    // Pick the masking policy for this request
+    let policy_request = PolicyRequest {
+        header: header_value(policy::POLICY_HEADER),
+        api_key: header_value(policy::API_KEY_HEADER),
+        identity: identity.as_ref().map(|Extension(ClientIdentity(id))| id.as_str()),
+        route: route_policy.as_ref().map(|Path(name)| name.as_str()),
+    };
+    let (policy_name, policy) = match state.policies.select(&policy_request) {
+        Ok(selected) => selected,
+        Err(e @ policy::PolicyError::NotAllowed(_)) => {
+            warn!("Rejected request: {}", e);
+            return (StatusCode::FORBIDDEN, e.to_string()).into_response();
+        }
+        Err(e) => {
+            warn!("Rejected request: {}", e);
+            return (StatusCode::BAD_REQUEST, e.to_string()).into_response();
+        }
+    };
+    debug!("Masking with policy '{}'", policy_name);
+
     // 1. Setup Streaming Pipeline via MPSC Channel
     // [ Incoming Body ] -> [ Masking Task ] -> [ tx ] ==> [ rx ] -> [ Upstream Request ]
     let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, std::io::Error>>(32);
 
+    // Refuse early when the declared size is already over the limit
+    let max_body_bytes = state.config.server.max_body_bytes;
+    let declared_len = header_value(header::CONTENT_LENGTH.as_str()).and_then(|v| v.parse::<usize>().ok());
+    if declared_len.is_some_and(|len| len > max_body_bytes) {
+        warn!("Rejected request: {}", StreamError::BodyTooLarge);
+        return (StatusCode::PAYLOAD_TOO_LARGE, StreamError::BodyTooLarge.to_string()).into_response();
+    }
+
     // Convert axum Body to a data stream
     let mut data_stream = body.into_data_stream();
 
     // 2. Spawn Background Masking Task
+    // A size violation aborts the upstream body; the flag turns the resulting error into a 413.
+    let too_large = Arc::new(AtomicBool::new(false));
+    let task_too_large = Arc::clone(&too_large);
+    let task_guard = Arc::clone(&limit_guard);
     tokio::spawn(async move {
-        let mut buffer: Vec<u8> = Vec::with_capacity(16 * 1024);
+        let _task_guard = task_guard;
+        let mut stream_masker = StreamMasker::new(policy, max_body_bytes);
 
         while let Some(chunk_result) = data_stream.next().await {
             match chunk_result {
-                Ok(chunk) => {
-                    buffer.extend_from_slice(&chunk);
-
-                    // Process lines if available (Typical for logs)
-                    if let Some(pos) = buffer.iter().rposition(|&b| b == b'\n') {
-                        let to_process = buffer[..=pos].to_vec();
-                        let tail = buffer[pos + 1..].to_vec();
-
-                        let text = String::from_utf8_lossy(&to_process);
-                        let masked = masker::apply_global_standard_masking(&text);
-                        
+                Ok(chunk) => match stream_masker.push(&chunk) {
+                    Ok(masked) if masked.is_empty() => {}
+                    Ok(masked) => {
                         if tx.send(Ok(Bytes::from(masked))).await.is_err() {
-                            break;
+                            return;
                         }
-                        buffer = tail;
                     }
-                    
-                    // Safety: If no newline but buffer is very large (e.g. 1MB),
-                    // we must process it to prevent memory bloat, but for standard 
-                    // logs and JSON objects under 2MB, we wait for completion or newline.
-                }
+                    Err(e) => {
+                        warn!("Aborting stream: {}", e);
+                        task_too_large.store(true, Ordering::SeqCst);
+                        let _ = tx.send(Err(std::io::Error::other(e))).await;
+                        return;
+                    }
+                },
                 Err(e) => {
                     error!("Error reading body stream: {}", e);
                     break;
@@ -70,15 +129,14 @@ pub async fn handle_log(
         }
 
         // 3. Final Flush (For single-line JSON or remaining data)
-        if !buffer.is_empty() {
-            let text = String::from_utf8_lossy(&buffer);
-            let masked = masker::apply_global_standard_masking(&text);
+        let masked = stream_masker.finish();
+        if !masked.is_empty() {
             let _ = tx.send(Ok(Bytes::from(masked))).await;
         }
     });
 
     // 3. Forward Masked Stream to Upstream Target
-    let target_url = &state.config.target.url;
+    let target_url = &state.upstream_url;
     let receiver_stream = ReceiverStream::new(rx);
     let upstream_body = reqwest::Body::wrap_stream(receiver_stream);
 
@@ -102,6 +160,9 @@ pub async fn handle_log(
                 .body(response_body)
                 .unwrap_or_else(|_| StatusCode::INTERNAL_SERVER_ERROR.into_response())
         }
+        Err(_) if too_large.load(Ordering::SeqCst) => {
+            (StatusCode::PAYLOAD_TOO_LARGE, "Request body or line exceeds the configured limit").into_response()
+        }
         Err(e) => {
             error!("Failed to forward to {}: {}", target_url, e);
             StatusCode::BAD_GATEWAY.into_response()
@@ -1,4 +1,27 @@
+pub mod address;
+pub mod asean;
+pub mod bank;
+pub mod card;
 pub mod config;
+pub mod denylist;
+pub mod dob;
+pub mod documents;
+pub mod encoded;
+pub mod field_rules;
+pub mod financial;
 pub mod masker;
+pub mod names;
+pub mod normalize;
+pub mod phone;
 pub mod validator;
 pub mod handlers;
+pub mod limits;
+pub mod network;
+pub mod policy;
+pub mod scoring;
+pub mod secrets;
+pub mod stream;
+pub mod terms;
+pub mod tls;
+pub mod url;
+pub mod wallet;
@@ -0,0 +1,252 @@
+use crate::config::LimitsConfig;
+use axum::http::{header, StatusCode};
+use axum::response::{IntoResponse, Response};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+/// Rate buckets kept at most; past this the least recently seen clients are forgotten.
+const MAX_TRACKED_CLIENTS: usize = 10_000;
+
+/// Buckets left after an eviction, so a burst of new clients doesn't evict on every request.
+const TRACKED_AFTER_EVICTION: usize = MAX_TRACKED_CLIENTS * 9 / 10;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum LimitRejection {
+    RateLimited { retry_after_secs: u64 },
+    TooManyStreams,
+    TooManyClientStreams,
+}
+
+impl std::fmt::Display for LimitRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitRejection::RateLimited { .. } => write!(f, "Rate limit exceeded"),
+            LimitRejection::TooManyStreams => write!(f, "Too many in-flight streams"),
+            LimitRejection::TooManyClientStreams => write!(f, "Too many in-flight streams for this client"),
+        }
+    }
+}
+
+impl IntoResponse for LimitRejection {
+    fn into_response(self) -> Response {
+        let retry_after = match self {
+            LimitRejection::RateLimited { retry_after_secs } => retry_after_secs,
+            _ => 1,
+        };
+        (
+            StatusCode::TOO_MANY_REQUESTS,
+            [(header::RETRY_AFTER, retry_after.to_string())],
+            self.to_string(),
+        )
+            .into_response()
+    }
+}
+
+/// In-flight stream count per client key.
+type ClientCounts = Arc<Mutex<HashMap<String, usize>>>;
+
+struct TokenBucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token-bucket rate limiting per client plus global and per-client caps on in-flight streams.
+pub struct Limiter {
+    config: LimitsConfig,
+    buckets: Mutex<HashMap<String, TokenBucket>>,
+    in_flight: Option<Arc<Semaphore>>,
+    per_client: ClientCounts,
+}
+
+/// Held for the lifetime of one `/mask` stream; releases its slots on drop.
+pub struct LimitGuard {
+    _global: Option<OwnedSemaphorePermit>,
+    client: Option<(String, ClientCounts)>,
+}
+
+impl Drop for LimitGuard {
+    fn drop(&mut self) {
+        if let Some((key, table)) = self.client.take() {
+            let mut table = table.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(count) = table.get_mut(&key) {
+                *count -= 1;
+                if *count == 0 {
+                    table.remove(&key);
+                }
+            }
+        }
+    }
+}
+
+impl Limiter {
+    pub fn new(config: LimitsConfig) -> Self {
+        let in_flight = config.max_in_flight.map(|n| Arc::new(Semaphore::new(n)));
+        Self {
+            config,
+            buckets: Mutex::new(HashMap::new()),
+            in_flight,
+            per_client: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Admits one request from `client` (API key, identity or IP), or says why not.
+    pub fn acquire(&self, client: &str) -> Result<LimitGuard, LimitRejection> {
+        self.acquire_at(client, Instant::now())
+    }
+
+    /// Stream slots are taken before a rate token, so a request turned away for lack of a slot
+    /// keeps the client's rate budget; a rate rejection hands the slots back as the guard drops.
+    fn acquire_at(&self, client: &str, now: Instant) -> Result<LimitGuard, LimitRejection> {
+        let global = match &self.in_flight {
+            Some(semaphore) => Some(
+                Arc::clone(semaphore)
+                    .try_acquire_owned()
+                    .map_err(|_| LimitRejection::TooManyStreams)?,
+            ),
+            None => None,
+        };
+        let mut guard = LimitGuard {
+            _global: global,
+            client: None,
+        };
+
+        if let Some(max) = self.config.max_in_flight_per_client {
+            let mut table = self.per_client.lock().unwrap_or_else(|e| e.into_inner());
+            let count = table.entry(client.to_string()).or_insert(0);
+            if *count >= max {
+                return Err(LimitRejection::TooManyClientStreams);
+            }
+            *count += 1;
+            guard.client = Some((client.to_string(), Arc::clone(&self.per_client)));
+        }
+
+        self.take_token(client, now)?;
+        Ok(guard)
+    }
+
+    fn take_token(&self, client: &str, now: Instant) -> Result<(), LimitRejection> {
+        let Some(rate) = self.config.requests_per_sec else {
+            return Ok(());
+        };
+        let burst = self.config.burst.unwrap_or(rate).max(1.0);
+
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        if buckets.len() >= MAX_TRACKED_CLIENTS && !buckets.contains_key(client) {
+            // Forgotten clients start over with a full bucket, so the longest idle go first
+            let mut by_age: Vec<(Instant, String)> =
+                buckets.iter().map(|(key, bucket)| (bucket.updated, key.clone())).collect();
+            by_age.sort_unstable();
+            let excess = buckets.len() - TRACKED_AFTER_EVICTION;
+            for (_, key) in by_age.into_iter().take(excess) {
+                buckets.remove(&key);
+            }
+        }
+
+        let bucket = buckets.entry(client.to_string()).or_insert(TokenBucket {
+            tokens: burst,
+            updated: now,
+        });
+        let elapsed = now.saturating_duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated = now;
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            let wait = (1.0 - bucket.tokens) / rate;
+            Err(LimitRejection::RateLimited {
+                retry_after_secs: wait.ceil().max(1.0) as u64,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_token_bucket_refills() {
+        let limiter = Limiter::new(LimitsConfig {
+            requests_per_sec: Some(1.0),
+            burst: Some(2.0),
+            ..Default::default()
+        });
+        let start = Instant::now();
+
+        assert!(limiter.acquire_at("ip:10.0.0.1", start).is_ok());
+        assert!(limiter.acquire_at("ip:10.0.0.1", start).is_ok());
+        assert_eq!(
+            limiter.acquire_at("ip:10.0.0.1", start).err(),
+            Some(LimitRejection::RateLimited { retry_after_secs: 1 })
+        );
+        // Other clients have their own bucket
+        assert!(limiter.acquire_at("ip:10.0.0.2", start).is_ok());
+        assert!(limiter.acquire_at("ip:10.0.0.1", start + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_concurrency_caps_release_on_drop() {
+        let limiter = Limiter::new(LimitsConfig {
+            max_in_flight: Some(2),
+            max_in_flight_per_client: Some(1),
+            ..Default::default()
+        });
+
+        let first = limiter.acquire("key:a").unwrap();
+        assert_eq!(limiter.acquire("key:a").err(), Some(LimitRejection::TooManyClientStreams));
+        let _second = limiter.acquire("key:b").unwrap();
+        assert_eq!(limiter.acquire("key:c").err(), Some(LimitRejection::TooManyStreams));
+
+        drop(first);
+        assert!(limiter.acquire("key:a").is_ok());
+    }
+
+    #[test]
+    fn test_stream_rejection_keeps_rate_budget() {
+        let limiter = Limiter::new(LimitsConfig {
+            requests_per_sec: Some(1.0),
+            burst: Some(2.0),
+            max_in_flight_per_client: Some(1),
+            ..Default::default()
+        });
+        let start = Instant::now();
+
+        let first = limiter.acquire_at("key:a", start).unwrap();
+        assert_eq!(limiter.acquire_at("key:a", start).err(), Some(LimitRejection::TooManyClientStreams));
+        drop(first);
+        let second = limiter.acquire_at("key:a", start).unwrap();
+        drop(second);
+        assert_eq!(
+            limiter.acquire_at("key:a", start).err(),
+            Some(LimitRejection::RateLimited { retry_after_secs: 1 })
+        );
+        // The rate rejection gave its stream slot back
+        assert!(limiter.acquire_at("key:a", start + Duration::from_secs(1)).is_ok());
+    }
+
+    #[test]
+    fn test_least_recently_seen_clients_evicted() {
+        let limiter = Limiter::new(LimitsConfig {
+            requests_per_sec: Some(1.0),
+            burst: Some(5.0),
+            ..Default::default()
+        });
+        let start = Instant::now();
+        for i in 0..MAX_TRACKED_CLIENTS {
+            let seen = start + Duration::from_millis(i as u64);
+            assert!(limiter.acquire_at(&format!("ip:{}", i), seen).is_ok());
+        }
+
+        let later = start + Duration::from_secs(60);
+        assert!(limiter.acquire_at("ip:new", later).is_ok());
+        let buckets = limiter.buckets.lock().unwrap();
+        assert_eq!(buckets.len(), TRACKED_AFTER_EVICTION + 1);
+        assert!(!buckets.contains_key("ip:0"));
+        assert!(buckets.contains_key(&format!("ip:{}", MAX_TRACKED_CLIENTS - 1)));
+    }
+}
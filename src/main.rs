@@ -3,11 +3,10 @@ use axum::{
     Router,
 };
 use axum::extract::DefaultBodyLimit;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use reqwest::Client;
-use std::time::Duration;
 use tower_http::trace::TraceLayer;
-use iron_mask_proxy::{config, handlers};
+use iron_mask_proxy::{config, handlers, limits, policy, tls};
 
 #[tokio::main]
 async fn main() {
@@ -29,30 +28,60 @@ async fn main() {
     
     let port = config.server.port;
     let host = config.server.host.clone();
+    let tls_settings = config.server.tls.clone();
+    let max_body_bytes = config.server.max_body_bytes;
 
     // 3. Setup Shared State
+    let (http_client, upstream_url) = match tls::build_upstream_client(&config.target) {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("❌ Failed to create upstream HTTP client: {}", e);
+            std::process::exit(1);
+        }
+    };
     let state = Arc::new(handlers::AppState {
-        http_client: Client::builder()
-            .timeout(Duration::from_millis(config.target.timeout_ms))
-            .build()
-            .expect("Failed to create HTTP client"),
+        http_client,
+        upstream_url,
         config: config.clone(),
+        policies: policy::PolicySet::from_config(&config),
+        limiter: limits::Limiter::new(config.limits.clone()),
     });
 
+    // Term lists backed by a file are re-read when it changes
+    for masking in std::iter::once(&config.masking).chain(config.policies.definitions.values()) {
+        if let Some(term_list) = &masking.term_list {
+            term_list.spawn_watcher();
+        }
+    }
+
     // 4. Setup Routes & Layers
     let app = Router::new()
         .route("/mask", post(handlers::handle_log))
+        .route("/mask/:policy", post(handlers::handle_log))
         .route("/healthz", get(handlers::health_check))
-        .layer(DefaultBodyLimit::max(2 * 1024 * 1024)) // 2MB limit
+        .layer(DefaultBodyLimit::max(max_body_bytes)) // /mask streams its body and enforces this itself
         .layer(TraceLayer::new_for_http())
         .with_state(state);
 
-    // 5. Start Server
+    // 5. Prepare TLS (optional)
+    let tls_reloader = match tls_settings {
+        Some(settings) => match tls::TlsReloader::new(settings) {
+            Ok(r) => Some(Arc::new(r)),
+            Err(e) => {
+                eprintln!("❌ Failed to load TLS configuration: {}", e);
+                std::process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // 6. Start Server
     let addr = format!("{}:{}", host, port);
+    let scheme = if tls_reloader.is_some() { "https" } else { "http" };
     
     println!("🚀 Iron Mask Proxy Professional Edition");
-    println!("📡 Listening on: http://{}", addr);
-    println!("💓 Health Check: http://{}/healthz", addr);
+    println!("📡 Listening on: {}://{}", scheme, addr);
+    println!("💓 Health Check: {}://{}/healthz", scheme, addr);
     
     let listener = match tokio::net::TcpListener::bind(&addr).await {
         Ok(l) => l,
@@ -63,10 +92,18 @@ async fn main() {
     };
     
     // Graceful Shutdown implementation
-    axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
-        .await
-        .expect("Server error");
+    match tls_reloader {
+        Some(reloader) => {
+            reloader.spawn_watcher();
+            tls::serve(listener, app, reloader, shutdown_signal()).await;
+        }
+        None => {
+            axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+                .with_graceful_shutdown(shutdown_signal())
+                .await
+                .expect("Server error");
+        }
+    }
 }
 
 async fn shutdown_signal() {
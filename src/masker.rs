@@ -1,118 +1,769 @@
+use crate::address;
+use crate::asean::{self, AseanIdKind, AseanToggles};
+use crate::bank::{self, BankKind};
+use crate::card;
+use crate::config::{MaskStrategy, MaskingConfig};
+use crate::dob;
+use crate::documents::{self, DocumentKind, DocumentToggles};
+use crate::encoded;
+use crate::field_rules::{self, FieldAction, FieldRule, PathStep};
+use crate::financial::{self, FinancialKind, FinancialToggles};
+use crate::network::{self, NetworkKind};
+use crate::normalize::NormalizedText;
+use crate::phone;
+use crate::scoring::{Scorer, is_allowed_value};
+use crate::secrets::{self, PemLine, PrivateKeyBlock};
+use crate::url;
 use crate::validator;
+use crate::wallet::{self, WalletKind};
 use lazy_static::lazy_static;
 use regex::Regex;
+use ring::hmac;
 use serde_json::Value;
+use std::borrow::Cow;
 
 lazy_static! {
-    // Phone: Matches 0 followed by digit 1-9, then 8-15 chars of digits or dashes.
-    // Example: 081-234-5678, 02-123-4567, 0991234567
-    static ref RE_PHONE: Regex = Regex::new(r"0[1-9][0-9-]{8,15}").unwrap();
-    
     // Email: Standard simple email regex
     static ref RE_EMAIL: Regex = Regex::new(r"(?i)[A-Z0-9._%+-]+@[A-Z0-9.-]+\.[A-Z]{2,}").unwrap();
     
-    // Credit Card: Matches sequence of digits/spaces/dashes, length 13-20
-    static ref RE_CREDIT_CARD: Regex = Regex::new(r"(\d[ -]*?){13,20}").unwrap();
+    // Thai ID: 13 contiguous digits, or the printed 1-4-5-2-1 layout with space/dash/dot
+    // Example: 1103700012346, 1-1037-00012-34-6, 1 1037 00012 34 6
+    // Confirmed by validator::is_thai_id
+    static ref RE_THAI_ID: Regex =
+        Regex::new(r"[0-9]{13}|[0-9][ .-][0-9]{4}[ .-][0-9]{5}[ .-][0-9]{2}[ .-][0-9]").unwrap();
+
+    static ref DEFAULT_MASKING: MaskingConfig = MaskingConfig::default();
+}
+
+pub fn mask_pii(value: &mut Value, depth: u8, config: &MaskingConfig) {
+    mask_value(value, depth, config, &mut Vec::new(), config.decode.max_depth);
 }
 
-#[allow(dead_code)]
-pub fn mask_pii(value: &mut Value, depth: u8, config: &crate::config::MaskingConfig) {
+/// `mask_pii` for the value at `path`; field rules select on the path, and the nearest key
+/// counts as context when scoring detections. `layers` is how many more encodings may be opened.
+/// `path` is one buffer for the whole walk: each level pushes its step and pops it when done.
+fn mask_value<'a>(value: &'a mut Value, depth: u8, config: &MaskingConfig, path: &mut Vec<PathStep<'a>>, layers: u8) {
     if depth > config.max_depth {
+        // Too deep to walk field by field, but still never let PII through
+        mask_overflow(value, config);
         return;
     }
 
     match value {
         Value::Object(map) => {
+            // A card number in this object makes its cvv/exp siblings sensitive too
+            let has_card = config.detectors.credit_card
+                && map.values().any(|v| match leaf_text(v) {
+                    Some(text) => card::contains_card(&text) && !is_allowed_value(&config.allow_values, &text),
+                    None => false,
+                });
+
             for (key, val) in map.iter_mut() {
                 // Check Whitelist (Exclude Fields)
                 if config.exclude_fields.contains(key) {
                     continue; // Skip masking for this field
                 }
 
-                let key_lower = key.to_lowercase();
-                if (key_lower.contains("name") || key_lower.contains("user")) && val.is_string() {
-                    if let Some(s) = val.as_str() {
-                        *val = Value::String(mask_name(s));
-                    }
+                path.push(PathStep::Key(key));
+                if let Some(rule) = config.field_rules.iter().find(|rule| rule.matches(path)) {
+                    apply_field_rule(rule, val, depth + 1, config, path, layers);
+                } else if leaf_text(val).is_some_and(|text| is_allowed_value(&config.allow_values, &text)) {
+                    // Allowed values stay as they are, whatever key they sit under
+                } else if config.detectors.password && secrets::is_password_key(key) && (val.is_string() || val.is_number()) {
+                    *val = Value::String("[REDACTED]".to_string());
+                } else if has_card && card::is_card_detail_key(key) && (val.is_string() || val.is_number()) {
+                    *val = Value::String("***".to_string());
+                } else if config.detectors.date_of_birth
+                    && dob::is_dob_key(key)
+                    && let Some(s) = val.as_str()
+                {
+                    // No date recognised (1990.05.12, "May 1990, 12th"): the whole value goes
+                    let masked = mask_birth_dates(s, config, None);
+                    let unparsed = masked == s && !s.trim().is_empty();
+                    *val = Value::String(if unparsed { "[REDACTED]".to_string() } else { masked });
+                } else if config.detectors.name
+                    && config.name_key_rule
+                    && field_rules::is_name_key(key)
+                    && let Some(s) = val.as_str()
+                {
+                    *val = Value::String(apply_strategy(config.strategies.name, s, mask_name));
                 } else {
-                    mask_pii(val, depth + 1, config);
+                    mask_value(val, depth + 1, config, path, layers);
                 }
+                path.pop();
             }
         }
         Value::Array(arr) => {
-            for val in arr.iter_mut() {
-                mask_pii(val, depth + 1, config);
+            for (index, val) in arr.iter_mut().enumerate() {
+                path.push(PathStep::Index(index));
+                match config.field_rules.iter().find(|rule| rule.matches(path)) {
+                    Some(rule) => apply_field_rule(rule, val, depth + 1, config, path, layers),
+                    None => mask_value(val, depth + 1, config, path, layers),
+                }
+                path.pop();
             }
         }
         Value::String(s) => {
-            // Apply streaming masking logic to long strings
-            if s.len() < 5000 {
-                *s = apply_global_standard_masking(s);
+            // Too long to scan at a bounded cost: dropped rather than passed through unchecked
+            if s.len() > config.max_string_bytes {
+                *s = "[REDACTED]".to_string();
+                return;
+            }
+            // JSON written into a string: {"msg":"{\"email\":\"a@b.com\"}"}
+            if layers > 0
+                && s.len() <= config.decode.max_bytes
+                && let Some(mut inner) = parse_json_container(s)
+            {
+                let before = inner.clone();
+                // Keys inside the decoded JSON don't outlive it, so it gets its own copy of the path
+                let mut inner_path: Vec<PathStep> = path.clone();
+                mask_value(&mut inner, depth + 1, config, &mut inner_path, layers - 1);
+                if inner != before {
+                    *s = inner.to_string();
+                }
+                return;
+            }
+            *s = mask_text(s, config, nearest_key(path), layers);
+        }
+        // Numbers long enough to be a phone, card or ID: {"card": 4532015112830366}
+        Value::Number(n) => {
+            let text = n.to_string();
+            // Any length once a deny list is set: {"customer_id": 123456}
+            if text.len() >= MIN_NUMERIC_PII_LEN || !config.deny_list.is_empty() {
+                let masked = mask_text(&text, config, nearest_key(path), layers);
+                if masked != text {
+                    *value = Value::String(masked);
+                }
             }
         }
         _ => {}
     }
 }
 
+/// Text of a string or number leaf; `None` for anything else.
+fn leaf_text(value: &Value) -> Option<Cow<'_, str>> {
+    match value {
+        Value::String(s) => Some(Cow::Borrowed(s)),
+        Value::Number(n) => Some(Cow::Owned(n.to_string())),
+        _ => None,
+    }
+}
+
+/// Shortest JSON number worth running the detectors on (a 7-digit local phone number).
+const MIN_NUMERIC_PII_LEN: usize = 7;
+
+/// The key closest to the value at `path`, skipping array indexes.
+fn nearest_key<'a>(path: &[PathStep<'a>]) -> Option<&'a str> {
+    path.iter().rev().find_map(|step| match step {
+        PathStep::Key(key) => Some(*key),
+        PathStep::Index(_) => None,
+    })
+}
+
+/// Carries out a matching `field_rules` entry on the value at `path`.
+fn apply_field_rule<'a>(
+    rule: &FieldRule,
+    value: &'a mut Value,
+    depth: u8,
+    config: &MaskingConfig,
+    path: &mut Vec<PathStep<'a>>,
+    layers: u8,
+) {
+    match rule.action {
+        FieldAction::Keep => {}
+        FieldAction::Redact => *value = Value::String("[REDACTED]".to_string()),
+        FieldAction::Hash => {
+            // Config validation rejects hash rules without a hash_key, so this never hashes unkeyed
+            let key = config.hash_key.as_deref().expect("hash field rule without a hash_key");
+            *value = Value::String(hash_value(value, key));
+        }
+        FieldAction::Mask => match value {
+            Value::String(s) => *s = apply_strategy(rule.strategy, s, mask_name),
+            Value::Number(n) => *value = Value::String(apply_strategy(rule.strategy, &n.to_string(), mask_name)),
+            // Objects and arrays are masked field by field as usual
+            _ => mask_value(value, depth, config, path, layers),
+        },
+    }
+}
+
+/// `hmac-sha256:<hex>` of a string value, or of the JSON text of anything else, under the
+/// deployment's `hash_key`; a plain hash of a phone number or ID could be reversed by trying them all.
+fn hash_value(value: &Value, key: &str) -> String {
+    let text = match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key.as_bytes());
+    let tag = hmac::sign(&key, text.as_bytes());
+    let hex: String = tag.as_ref().iter().map(|b| format!("{:02x}", b)).collect();
+    format!("hmac-sha256:{}", hex)
+}
+
+/// Text-masks a subtree below `max_depth` as a whole; redacts it if the result is no longer valid JSON.
+fn mask_overflow(value: &mut Value, config: &MaskingConfig) {
+    let raw = value.to_string();
+    let masked = apply_masking(&raw, config);
+    if masked != raw {
+        *value = serde_json::from_str(&masked).unwrap_or_else(|_| Value::String("[REDACTED]".to_string()));
+    }
+}
+
+/// Masks a chunk of the request body line by line. Lines holding a JSON object or array
+/// go through `mask_pii` so `exclude_fields` and key rules apply; anything else is plain text.
+pub fn mask_payload(input: &str, config: &MaskingConfig) -> String {
+    let mut private_key = PrivateKeyBlock::default();
+    let mut result = mask_lines(input, config, &mut private_key);
+    result.push_str(&finish_lines(config, &mut private_key));
+    result
+}
+
+/// `mask_payload` for a stream fed in pieces: `private_key` carries an open PEM block from
+/// one call to the next; its body lines are held until END drops them with it.
+pub fn mask_lines(input: &str, config: &MaskingConfig, private_key: &mut PrivateKeyBlock) -> String {
+    let mut result = String::with_capacity(input.len());
+    for line in input.split_inclusive('\n') {
+        if config.detectors.private_key {
+            match private_key.feed(line) {
+                PemLine::Held | PemLine::Closed => continue,
+                PemLine::Released(held) => held.iter().for_each(|held| result.push_str(&mask_line(held, config))),
+                PemLine::Outside => {}
+            }
+        }
+        result.push_str(&mask_line(line, config));
+    }
+    result
+}
+
+/// Masks the lines a PEM block still holds when the input ends without its END line.
+pub fn finish_lines(config: &MaskingConfig, private_key: &mut PrivateKeyBlock) -> String {
+    private_key.release().iter().map(|line| mask_line(line, config)).collect()
+}
+
+/// One line of a payload: a JSON object or array goes through `mask_pii`, anything else is text.
+fn mask_line(line: &str, config: &MaskingConfig) -> String {
+    let content = line.trim_end_matches(['\r', '\n']);
+    let ending = &line[content.len()..];
+    let trimmed = content.trim_start();
+
+    if (trimmed.starts_with('{') || trimmed.starts_with('['))
+        && let Ok(mut value) = serde_json::from_str::<Value>(content)
+    {
+        mask_pii(&mut value, 0, config);
+        return format!("{}{}", value, ending);
+    }
+    apply_masking(line, config)
+}
+
+/// Masks free text with the default policy (every detector on, partial masks).
 pub fn apply_global_standard_masking(input: &str) -> String {
-    let mut result = input.to_string();
-    let id_pattern = Regex::new(r"\d{13}").unwrap();
+    apply_masking(input, &DEFAULT_MASKING)
+}
+
+pub fn apply_masking(input: &str, config: &MaskingConfig) -> String {
+    mask_text(input, config, None, config.decode.max_depth)
+}
+
+/// A JSON object or array, if that is what `text` holds.
+fn parse_json_container(text: &str) -> Option<Value> {
+    let trimmed = text.trim_start();
+    if !trimmed.starts_with('{') && !trimmed.starts_with('[') {
+        return None;
+    }
+    serde_json::from_str::<Value>(text).ok()
+}
 
-    // 1. Thai ID
-    for mat in id_pattern.find_iter(input) {
-        if validator::is_thai_id(mat.as_str()) {
-            let masked = format!("{}XXXXXX{}", &mat.as_str()[0..3], &mat.as_str()[9..13]);
-            result = result.replace(mat.as_str(), &masked);
+/// Masks text taken out of an encoding: field by field if it is JSON, else as free text.
+fn mask_decoded(decoded: &str, config: &MaskingConfig, key: Option<&str>, layers: u8) -> String {
+    match parse_json_container(decoded) {
+        Some(mut value) => {
+            let before = value.clone();
+            mask_value(&mut value, 0, config, &mut Vec::new(), layers);
+            if value == before { decoded.to_string() } else { value.to_string() }
         }
+        None => mask_text(decoded, config, key, layers),
     }
+}
 
-    // 2. Credit Card
-    result = RE_CREDIT_CARD
-        .replace_all(&result, |caps: &regex::Captures| {
-            let match_str = &caps[0];
-            let digits: String = match_str.chars().filter(|c| c.is_ascii_digit()).collect();
-            
-            // Validate Length (13-19) and Luhn Algorithm
-            if digits.len() >= 13 && digits.len() <= 19 && validator::is_luhn_valid(&digits) {
-                if match_str.len() > 8 {
-                     let prefix = &match_str[0..4];
-                     let suffix = &match_str[match_str.len()-4..];
-                     format!("{}********{}", prefix, suffix)
-                } else {
-                    match_str.to_string()
+/// `apply_masking` for a JSON string value under `key`, opening up to `layers` encodings.
+fn mask_text(input: &str, config: &MaskingConfig, key: Option<&str>, layers: u8) -> String {
+    // Credentials (tokens, keys, passwords) before any digit detector can cut into them
+    let result = secrets::mask_secrets(input, &config.detectors);
+
+    // URLs are masked part by part; the text around them goes through the detectors as usual
+    let urls = if config.detectors.url { url::find_urls(&result) } else { Vec::new() };
+    if urls.is_empty() {
+        return mask_detected(&result, config, key, layers);
+    }
+    let masked_urls: Vec<String> = urls
+        .iter()
+        .map(|range| {
+            url::mask_url(
+                &result[range.clone()],
+                |text, previous| mask_text(text, config, previous, layers),
+                |name, value| mask_param(name, value, config, layers),
+            )
+        })
+        .collect();
+
+    // The rest of the line is masked in one piece, each URL standing in as a placeholder, so a
+    // keyword before a URL still counts for a number after it
+    if !result.contains(URL_PLACEHOLDER) {
+        let mut outside = String::with_capacity(result.len());
+        let mut last = 0;
+        for range in &urls {
+            outside.push_str(&result[last..range.start]);
+            outside.push(URL_PLACEHOLDER);
+            last = range.end;
+        }
+        outside.push_str(&result[last..]);
+        let detected = mask_detected(&outside, config, key, layers);
+        if detected.matches(URL_PLACEHOLDER).count() == urls.len() {
+            let mut masked = String::with_capacity(detected.len());
+            for (i, piece) in detected.split(URL_PLACEHOLDER).enumerate() {
+                if i > 0 {
+                    masked.push_str(&masked_urls[i - 1]);
                 }
-            } else {
-                match_str.to_string()
+                masked.push_str(piece);
             }
-        })
-        .to_string();
-
-    // 3. Email
-    result = RE_EMAIL
-        .replace_all(&result, |caps: &regex::Captures| mask_email(&caps[0]))
-        .to_string();
-
-    // 4. Phone
-    result = RE_PHONE
-        .replace_all(&result, |caps: &regex::Captures| {
-            let match_str = &caps[0];
-            let digits: String = match_str.chars().filter(|c| c.is_ascii_digit()).collect();
-            
-            if digits.len() >= 9 && digits.len() <= 10 {
-                 if digits.len() == 10 {
-                    // Mobile: 081-234-5678 -> 081XXXXX78
-                    format!("{}XXXXX{}", &digits[0..3], &digits[8..10])
+            return masked;
+        }
+    }
+
+    // A detector took a placeholder into its mask: fall back to the pieces between URLs
+    let mut masked = String::with_capacity(result.len());
+    let mut last = 0;
+    for (range, masked_url) in urls.iter().zip(&masked_urls) {
+        masked.push_str(&mask_detected(&result[last..range.start], config, key, layers));
+        masked.push_str(masked_url);
+        last = range.end;
+    }
+    masked.push_str(&mask_detected(&result[last..], config, key, layers));
+    masked
+}
+
+/// Stands in for a URL while the text around it is masked (Unicode private use, matched by no detector).
+const URL_PLACEHOLDER: char = '\u{E000}';
+
+/// A query parameter, masked the way a JSON field of the same name would be.
+fn mask_param(name: &str, value: &str, config: &MaskingConfig, layers: u8) -> String {
+    let mut field = Value::Object([(name.to_string(), Value::String(value.to_string()))].into_iter().collect());
+    mask_value(&mut field, 0, config, &mut Vec::new(), layers);
+    match &field[name] {
+        Value::String(masked) => masked.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Runs the detectors over text with its credentials masked and no URL in it.
+fn mask_detected(input: &str, config: &MaskingConfig, key: Option<&str>, layers: u8) -> String {
+    if input.is_empty() {
+        return String::new();
+    }
+    let mut result = input.to_string();
+    let detectors = &config.detectors;
+    let strategies = &config.strategies;
+    // Detections scoring under the policy threshold (e.g. a number after "order") stay as they are
+    let scorer = Scorer::new(&config.scoring, &config.allow_values, key);
+
+    // Values on the policy's deny list, whatever they look like
+    if !config.deny_list.is_empty() {
+        for range in config.deny_list.find(&result).into_iter().rev() {
+            result.replace_range(range, "[REDACTED]");
+        }
+    }
+
+    // Dictionary terms (codenames, VIP and partner names), whole, before any detector cuts into them.
+    // Listed on purpose, so like the deny list they are never scored away.
+    if let Some(term_list) = &config.term_list {
+        let spans: Vec<_> = term_list
+            .current()
+            .find(&result)
+            .into_iter()
+            .map(|range| {
+                let masked = apply_strategy(strategies.term, &result[range.clone()], mask_name);
+                (range, masked)
+            })
+            .collect();
+        for (range, masked) in spans.into_iter().rev() {
+            result.replace_range(range, &masked);
+        }
+    }
+
+    // Base64 blobs and %XX runs are decoded, masked inside and re-encoded as they were
+    if layers > 0 {
+        result = encoded::mask_encoded(&result, config.decode.max_bytes, |decoded| {
+            mask_decoded(decoded, config, key, layers - 1)
+        });
+    }
+
+    // From here on detectors run on a copy with Thai/full-width digits folded to ASCII and zero-width
+    // characters removed; masks are spliced back into the original text in its own script.
+
+    // Network addresses next, so a dotted quad is never read as a phone or card number
+    if detectors.ip_address || detectors.mac_address {
+        let normalized = NormalizedText::new(&result);
+        let prefix = config.network_prefix;
+        let spans = network::find_network(&normalized.text, detectors.ip_address, detectors.mac_address)
+            .into_iter()
+            .filter(|found| scorer.keep(found.kind.detector(), &normalized.text, found.range.start))
+            .map(|found| {
+                let value = &normalized.text[found.range.clone()];
+                let masked = match (found.kind, strategies.ip_address) {
+                    (NetworkKind::Ipv4(addr), MaskStrategy::Truncate) => network::truncate_ipv4(addr, prefix.ipv4),
+                    (NetworkKind::Ipv6(addr), MaskStrategy::Truncate) => network::truncate_ipv6(addr, prefix.ipv6),
+                    (NetworkKind::Ipv4(addr), strategy) => {
+                        apply_strategy(strategy, value, |_| network::mask_ipv4(addr, prefix.ipv4))
+                    }
+                    (NetworkKind::Ipv6(addr), strategy) => {
+                        apply_strategy(strategy, value, |_| network::mask_ipv6(addr, prefix.ipv6))
+                    }
+                    (NetworkKind::Mac, _) => apply_strategy(strategies.mac_address, value, network::mask_mac),
+                };
+                (found.range, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // IBAN, SWIFT/BIC and routing numbers, before their digits can pass for cards or IDs
+    let toggles = FinancialToggles {
+        iban: detectors.iban,
+        swift_bic: detectors.swift_bic,
+        aba_routing: detectors.aba_routing,
+    };
+    if toggles.iban || toggles.swift_bic || toggles.aba_routing {
+        let normalized = NormalizedText::new(&result);
+        let spans = financial::find_financial_ids(&normalized.text, &toggles)
+            .into_iter()
+            .filter(|found| scorer.keep(found.kind.detector(), &normalized.text, found.start))
+            .map(|found| {
+                let value = &normalized.text[found.start..found.end];
+                let masked = match found.kind {
+                    // GB82 WEST 1234 5698 7654 32 -> GB82 **** **** **** **54 32
+                    FinancialKind::Iban => apply_strategy(strategies.iban, value, |v| keep_ends(v, 4, 4)),
+                    // BKKBTHBK -> BKKB****
+                    FinancialKind::SwiftBic => apply_strategy(strategies.swift_bic, value, |v| keep_ends(v, 4, 0)),
+                    FinancialKind::AbaRouting => apply_strategy(strategies.aba_routing, value, |v| keep_ends(v, 0, 4)),
+                };
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Crypto wallet addresses, before their digit runs can pass for phones or IDs
+    if detectors.bitcoin_address || detectors.ethereum_address {
+        let normalized = NormalizedText::new(&result);
+        let spans = wallet::find_wallets(&normalized.text, detectors.bitcoin_address, detectors.ethereum_address)
+            .into_iter()
+            .filter(|found| {
+                if found.checksummed {
+                    scorer.keep_validated(found.kind.detector(), &normalized.text, found.start)
                 } else {
-                    // Landline: 02-123-4567 -> 02XXXX67
-                     format!("{}XXXX{}", &digits[0..2], &digits[7..9])
+                    scorer.keep(found.kind.detector(), &normalized.text, found.start)
                 }
-            } else {
-                match_str.to_string()
+            })
+            .map(|found| {
+                let value = &normalized.text[found.start..found.end];
+                let masked = match found.kind {
+                    // 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa -> 1A1z**************************vfNa
+                    WalletKind::Bitcoin => apply_strategy(strategies.bitcoin_address, value, |v| keep_ends(v, 4, 4)),
+                    // 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed -> 0x5aAe********************************eAed
+                    WalletKind::Ethereum => {
+                        apply_strategy(strategies.ethereum_address, value, |v| keep_ends(v, 6, 4))
+                    }
+                };
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Bank accounts and PromptPay IDs, before the Thai ID and phone steps claim their digits
+    if detectors.bank_account || detectors.promptpay {
+        let normalized = NormalizedText::new(&result);
+        let spans = bank::find_bank_ids(&normalized.text, detectors.bank_account, detectors.promptpay)
+            .into_iter()
+            .filter(|found| scorer.keep(found.kind.detector(), &normalized.text, found.start))
+            .map(|found| {
+                let value = &normalized.text[found.start..found.end];
+                let masked = match found.kind {
+                    // 123-4-56789-0 -> XXX-X-XX789-0
+                    BankKind::Account => apply_strategy(strategies.bank_account, value, |v| mask_digits(v, 0, 4)),
+                    BankKind::PromptPayPhone => {
+                        apply_strategy(strategies.promptpay, value, |v| phone::mask_phone(v, 3))
+                    }
+                    BankKind::PromptPayNationalId | BankKind::PromptPayWallet => {
+                        apply_strategy(strategies.promptpay, value, |v| mask_digits(v, 3, 4))
+                    }
+                };
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Document numbers that follow their keyword (passport, license, plate, social security)
+    let toggles = DocumentToggles {
+        passport: detectors.passport,
+        driver_license: detectors.driver_license,
+        vehicle_plate: detectors.vehicle_plate,
+        social_security: detectors.social_security,
+    };
+    if toggles.passport || toggles.driver_license || toggles.vehicle_plate || toggles.social_security {
+        let normalized = NormalizedText::new(&result);
+        let spans = documents::find_documents(&normalized.text, &toggles)
+            .into_iter()
+            .filter(|found| scorer.keep(found.kind.detector(), &normalized.text, found.start))
+            .map(|found| {
+                let value = &normalized.text[found.start..found.end];
+                let masked = match found.kind {
+                    // AA1234567 -> AAXXXXX67
+                    DocumentKind::Passport => apply_strategy(strategies.passport, value, |v| mask_digits(v, 0, 2)),
+                    DocumentKind::DriverLicense => {
+                        apply_strategy(strategies.driver_license, value, |v| mask_digits(v, 0, 2))
+                    }
+                    // กข 1234 -> กข XXXX
+                    DocumentKind::VehiclePlate => {
+                        apply_strategy(strategies.vehicle_plate, value, |v| mask_digits(v, 0, 0))
+                    }
+                    DocumentKind::SocialSecurity => {
+                        apply_strategy(strategies.social_security, value, |v| mask_digits(v, 3, 4))
+                    }
+                };
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Birth dates after dob/birth/วันเกิด, generalised rather than hidden
+    if detectors.date_of_birth {
+        result = mask_birth_dates(&result, config, Some(&scorer));
+    }
+
+    // Singapore, Malaysian, Indonesian and Vietnamese national IDs
+    let toggles = AseanToggles {
+        sg_nric: detectors.sg_nric,
+        my_mykad: detectors.my_mykad,
+        id_nik: detectors.id_nik,
+        vn_cccd: detectors.vn_cccd,
+    };
+    if toggles.sg_nric || toggles.my_mykad || toggles.id_nik || toggles.vn_cccd {
+        let normalized = NormalizedText::new(&result);
+        let spans = asean::find_asean_ids(&normalized.text, &toggles, key)
+            .into_iter()
+            .filter(|found| scorer.keep(found.kind.detector(), &normalized.text, found.start))
+            .map(|found| {
+                let value = &normalized.text[found.start..found.end];
+                let masked = match found.kind {
+                    // S1234567D -> SXXXX567D
+                    AseanIdKind::SgNric => apply_strategy(strategies.sg_nric, value, |v| mask_digits(v, 0, 3)),
+                    // 900101-14-5678 -> XXXXXX-XX-5678
+                    AseanIdKind::MyMykad => apply_strategy(strategies.my_mykad, value, |v| mask_digits(v, 0, 4)),
+                    AseanIdKind::IdNik => apply_strategy(strategies.id_nik, value, |v| mask_digits(v, 0, 4)),
+                    AseanIdKind::VnCccd => apply_strategy(strategies.vn_cccd, value, |v| mask_digits(v, 0, 4)),
+                };
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Thai ID
+    if detectors.thai_id {
+        let normalized = NormalizedText::new(&result);
+        let bytes = normalized.text.as_bytes();
+        let spans = RE_THAI_ID
+            .find_iter(&normalized.text)
+            .filter(|mat| {
+                let digits: String = mat.as_str().chars().filter(|c| c.is_ascii_digit()).collect();
+                // A grouped layout must not be a piece of a longer number
+                let grouped = digits.len() != mat.len();
+                let glued = (mat.start() > 0 && bytes[mat.start() - 1].is_ascii_digit())
+                    || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_digit());
+                validator::is_thai_id(&digits)
+                    && !(grouped && glued)
+                    && scorer.keep("thai_id", &normalized.text, mat.start())
+            })
+            .map(|mat| {
+                // 1103700012346 -> 110XXXXXX2346, 1-1037-00012-34-6 -> 1-10XX-XXXX2-34-6
+                let masked = apply_strategy(strategies.thai_id, mat.as_str(), |id| mask_digits(id, 3, 4));
+                (mat.range(), masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Credit Card
+    if detectors.credit_card {
+        let normalized = NormalizedText::new(&result);
+        // IIN/BIN, network length and Luhn are checked in card::find_cards
+        let mut cards = card::find_cards(&normalized.text);
+        cards.retain(|range| scorer.keep("credit_card", &normalized.text, range.start));
+        let mut spans: Vec<_> = cards
+            .iter()
+            .map(|range| {
+                let match_str = &normalized.text[range.clone()];
+                let masked = match strategies.credit_card {
+                    // PCI DSS display rule: 4532-01**-****-0366
+                    MaskStrategy::Pci => keep_ends(match_str, 6, 4),
+                    strategy => apply_strategy(strategy, match_str, |card| {
+                        if card.len() > 8 {
+                            let prefix = &card[0..4];
+                            let suffix = &card[card.len() - 4..];
+                            format!("{}********{}", prefix, suffix)
+                        } else {
+                            card.to_string()
+                        }
+                    }),
+                };
+                (range.clone(), masked)
+            })
+            .collect();
+        // CVV and expiry written next to a card go with it
+        spans.extend(card::find_card_details(&normalized.text, &cards));
+        spans.sort_by_key(|(range, _)| range.start);
+        result = normalized.splice(&result, spans);
+    }
+
+    // Email
+    if detectors.email {
+        let normalized = NormalizedText::new(&result);
+        let spans = RE_EMAIL
+            .find_iter(&normalized.text)
+            .filter(|mat| scorer.keep("email", &normalized.text, mat.start()))
+            .map(|mat| (mat.range(), apply_strategy(strategies.email, mat.as_str(), mask_email)))
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Phone (national and international, see phone.rs for numbering plans)
+    if detectors.phone {
+        let normalized = NormalizedText::new(&result);
+        let spans = phone::find_phones(&normalized.text)
+            .into_iter()
+            .filter(|found| scorer.keep("phone", &normalized.text, found.start))
+            .map(|found| {
+                let match_str = &normalized.text[found.start..found.end];
+                let masked = apply_strategy(strategies.phone, match_str, |p| {
+                    // 081-234-5678 -> 081-XXX-XX78, +66 2 123 4567 -> +66 2 XXX XX67
+                    phone::mask_phone(p, found.keep_prefix_digits)
+                });
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Postal addresses, generalised to the province: ... เขตวัฒนา กรุงเทพมหานคร 10110 -> [ADDRESS:กรุงเทพมหานคร]
+    if detectors.address {
+        let normalized = NormalizedText::new(&result);
+        let spans = address::find_addresses(&normalized.text)
+            .into_iter()
+            .filter(|found| scorer.keep("address", &normalized.text, found.start))
+            .map(|found| {
+                let value = &normalized.text[found.start..found.end];
+                let masked = apply_strategy(strategies.address, value, |_| format!("[ADDRESS:{}]", found.province));
+                (found.start..found.end, masked)
+            })
+            .collect();
+        result = normalized.splice(&result, spans);
+    }
+
+    // Names after an honorific: คุณสมชาย -> คุณสม***
+    if detectors.name {
+        let spans: Vec<_> = config
+            .name_dictionary
+            .find_names(&result)
+            .into_iter()
+            .filter(|range| scorer.keep("name", &result, range.start))
+            .map(|range| {
+                let masked = apply_strategy(strategies.name, &result[range.clone()], mask_name);
+                (range, masked)
+            })
+            .collect();
+        for (range, masked) in spans.into_iter().rev() {
+            result.replace_range(range, &masked);
+        }
+    }
+
+    result
+}
+
+/// Generalises birth dates in `input`: day and month hidden, or year only, or an age band.
+/// Free text (with a `scorer`) needs a dob/birth keyword; values of birth date JSON keys do not.
+fn mask_birth_dates(input: &str, config: &MaskingConfig, scorer: Option<&Scorer>) -> String {
+    let normalized = NormalizedText::new(input);
+    let today = dob::Date::today();
+    let spans = dob::find_dates(&normalized.text, scorer.is_some())
+        .into_iter()
+        .filter(|found| scorer.is_none_or(|scorer| scorer.keep("date_of_birth", &normalized.text, found.start)))
+        .map(|found| {
+            let value = &normalized.text[found.start..found.end];
+            let masked = match config.strategies.date_of_birth {
+                // 12/05/2530 -> 2530
+                MaskStrategy::Year => normalized.text[found.year.clone()].to_string(),
+                // 12/05/2530 -> 30-39
+                MaskStrategy::AgeBand => dob::age_band(found.birth, today, config.age_band_years),
+                // 12/05/2530 -> XX/XX/2530
+                strategy => apply_strategy(strategy, value, |_| dob::mask_day_month(&normalized.text, &found)),
+            };
+            (found.start..found.end, masked)
+        })
+        .collect();
+    normalized.splice(input, spans)
+}
+
+/// Applies a configured strategy; `partial` is the detector's own masking format.
+fn apply_strategy(strategy: MaskStrategy, value: &str, partial: impl FnOnce(&str) -> String) -> String {
+    match strategy {
+        MaskStrategy::Partial
+        | MaskStrategy::Truncate
+        | MaskStrategy::Pci
+        | MaskStrategy::Year
+        | MaskStrategy::AgeBand => partial(value),
+        MaskStrategy::KeepLast4 => keep_last(value, 4),
+        MaskStrategy::Redact => "[REDACTED]".to_string(),
+    }
+}
+
+/// Replaces digits with X except the first `keep_prefix` and last `keep_suffix`;
+/// separators stay where they are.
+pub(crate) fn mask_digits(value: &str, keep_prefix: usize, keep_suffix: usize) -> String {
+    let total = value.chars().filter(|c| c.is_ascii_digit()).count();
+    let mut index = 0;
+    value
+        .chars()
+        .map(|c| {
+            if !c.is_ascii_digit() {
+                return c;
             }
+            index += 1;
+            if index <= keep_prefix || index + keep_suffix > total { c } else { 'X' }
         })
-        .to_string();
+        .collect()
+}
 
-    result
+/// Stars out every letter/digit except the last `n`, keeping separators in place.
+fn keep_last(value: &str, n: usize) -> String {
+    keep_ends(value, 0, n)
+}
+
+/// Stars out every letter/digit except the first `head` and last `tail`, keeping separators in place.
+fn keep_ends(value: &str, head: usize, tail: usize) -> String {
+    let total = value.chars().filter(|c| c.is_alphanumeric()).count();
+    let mut seen = 0;
+    value
+        .chars()
+        .map(|c| {
+            if !c.is_alphanumeric() {
+                return c;
+            }
+            seen += 1;
+            if seen <= head || seen + tail > total { c } else { '*' }
+        })
+        .collect()
 }
 
 fn mask_email(email: &str) -> String {
@@ -132,7 +783,6 @@ fn mask_email(email: &str) -> String {
     }
 }
 
-#[allow(dead_code)]
 pub fn mask_name(name: &str) -> String {
     let chars: Vec<char> = name.chars().collect();
     if chars.len() <= 1 {
@@ -154,9 +804,296 @@ mod chaos_tests {
 
         assert!(masked.contains("081XXXXX78"));
         assert!(masked.contains("te***@test.com"));
+        assert!(masked.starts_with("ติดต่อคุณสม***ที่เบอร์"));
         // Note: Thai ID might not pass checksum if random, so we focus on phone/email here
     }
 
+    #[test]
+    fn test_address_generalised_to_province() {
+        let masked = apply_global_standard_masking("ส่งของ 99 ม.5 ต.สุเทพ อ.เมืองเชียงใหม่ จ.เชียงใหม่ ๕๐๒๐๐ ด่วน");
+        assert_eq!(masked, "ส่งของ [ADDRESS:เชียงใหม่] ด่วน");
+    }
+
+    #[test]
+    fn test_birth_dates_generalised() {
+        assert_eq!(apply_global_standard_masking("วันเกิด ๑๒/๐๕/๒๕๓๐"), "วันเกิด XX/XX/๒๕๓๐");
+
+        let mut config = MaskingConfig::default();
+        config.strategies.date_of_birth = MaskStrategy::Year;
+        let mut value = json!({"dob": "1987-05-12", "shipped": "2024-01-02"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"dob": "1987", "shipped": "2024-01-02"}));
+
+        let mut value = json!({"dob": "1990.05.12", "birth_date": "May 1990, 12th", "dob_note": ""});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"dob": "[REDACTED]", "birth_date": "[REDACTED]", "dob_note": ""}));
+    }
+
+    #[test]
+    fn test_reference_numbers_left_alone() {
+        // Penalising keywords are opt-in
+        let mut config = MaskingConfig::default();
+        config.scoring.keywords.insert(
+            "phone".to_string(),
+            crate::config::KeywordConfig {
+                boost: vec![],
+                penalty: vec!["order".to_string()],
+            },
+        );
+        assert_eq!(apply_masking("order 0812345678", &config), "order 0812345678");
+        assert_eq!(apply_masking("เบอร์ 0812345678", &config), "เบอร์ 081XXXXX78");
+
+        let mut value = json!({"order_id": "0812345678", "mobile": "0812345678"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"order_id": "0812345678", "mobile": "081XXXXX78"}));
+
+        // By default nothing is unmasked, and a validated card or Thai ID never is
+        assert_eq!(apply_global_standard_masking("refund 4532015112830366"), "refund 4532********0366");
+        assert_eq!(apply_global_standard_masking("serial 1103700012346"), "serial 110XXXXXX2346");
+        assert_eq!(apply_global_standard_masking("ticket from 0812345678"), "ticket from 081XXXXX78");
+        let mut value = json!({"note": "ref 1103700012346", "order_id": "4532015112830366"});
+        mask_pii(&mut value, 0, &MaskingConfig::default());
+        assert_eq!(value, json!({"note": "ref 110XXXXXX2346", "order_id": "4532********0366"}));
+    }
+
+    #[test]
+    fn test_allowed_values_under_sensitive_keys() {
+        let config = MaskingConfig {
+            allow_values: vec!["QA Tester".to_string(), "4242 4242 4242 4242".to_string()],
+            ..Default::default()
+        };
+        let mut value = json!({
+            "customer_name": "QA Tester",
+            "password": "qa tester",
+            "payment": {"card": "4242424242424242", "cvv": "123"},
+            "owner_name": "Somchai"
+        });
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(
+            value,
+            json!({
+                "customer_name": "QA Tester",
+                "password": "qa tester",
+                "payment": {"card": "4242424242424242", "cvv": "123"},
+                "owner_name": "So***"
+            })
+        );
+    }
+
+    #[test]
+    fn test_oversized_strings_redacted() {
+        let config = MaskingConfig {
+            max_string_bytes: 32,
+            ..Default::default()
+        };
+        let long = format!("call 0812345678 {}", "x".repeat(32));
+        let mut value = json!({"note": long, "short": "call 0812345678"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"note": "[REDACTED]", "short": "call 081XXXXX78"}));
+    }
+
+    #[test]
+    fn test_password_keys_by_component() {
+        let mut value = json!({"db_password": "hunter2", "newPassword": "s3cret", "passport_no": "AA1234567"});
+        mask_pii(&mut value, 0, &MaskingConfig::default());
+        assert_eq!(value["db_password"], "[REDACTED]");
+        assert_eq!(value["newPassword"], "[REDACTED]");
+        assert_ne!(value["passport_no"], "[REDACTED]");
+    }
+
+    #[test]
+    fn test_field_rules() {
+        let config: MaskingConfig = serde_yaml::from_str(
+            r#"
+hash_key: "test-deployment-key"
+field_rules:
+  - { path: "$.customer.*.email", action: redact }
+  - { key: "*_nm", action: mask }
+  - { key: "username_hash", action: keep }
+  - { path: "**.card_ref", action: hash }
+"#,
+        )
+        .unwrap();
+        let mut value = json!({
+            "customer": {"home": {"email": "a@b.co"}},
+            "full_nm": "Somchai",
+            "username_hash": "ab12",
+            "filename": "report.pdf",
+            "items": [{"card_ref": "x"}]
+        });
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value["customer"]["home"]["email"], "[REDACTED]");
+        assert_eq!(value["full_nm"], "So***");
+        assert_eq!(value["username_hash"], "ab12");
+        // Still the default rule: "filename" contains "name"
+        assert_eq!(value["filename"], "re***");
+        assert_eq!(
+            value["items"][0]["card_ref"],
+            "hmac-sha256:e895e37deb6ebb4045bbe222be07563f29144bf9d5fbb4532eacfcd9cc96d516"
+        );
+
+        let config = MaskingConfig {
+            name_key_rule: false,
+            ..Default::default()
+        };
+        let mut value = json!({"filename": "report.pdf"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value["filename"], "report.pdf");
+    }
+
+    #[test]
+    fn test_encoded_payloads() {
+        let mut value = json!({"msg": "{\"email\":\"john@test.com\",\"name\":\"Somchai\"}"});
+        mask_pii(&mut value, 0, &MaskingConfig::default());
+        assert_eq!(value["msg"], r#"{"email":"jo***@test.com","name":"So***"}"#);
+
+        // Numbers inside the decoded JSON are masked too
+        let mut value = json!({"msg": "{\"card\": 4532015112830366, \"id\": 1103700012346, \"qty\": 2}"});
+        mask_pii(&mut value, 0, &MaskingConfig::default());
+        assert_eq!(value["msg"], r#"{"card":"4532********0366","id":"110XXXXXX2346","qty":2}"#);
+
+        assert_eq!(apply_global_standard_masking("to=john%40test.com"), "to=jo%2A%2A%2A%40test.com");
+
+        use base64::Engine;
+        use base64::engine::general_purpose::STANDARD;
+        let blob = STANDARD.encode(r#"{"phone":"0812345678"}"#);
+        let masked = apply_global_standard_masking(&format!("body={}", blob));
+        let decoded = STANDARD.decode(&masked["body=".len()..]).unwrap();
+        assert_eq!(decoded, br#"{"phone":"081XXXXX78"}"#);
+
+        let config = MaskingConfig {
+            decode: crate::config::DecodeConfig { max_depth: 0, max_bytes: 1024 },
+            ..Default::default()
+        };
+        assert_eq!(apply_masking("to=john%40test.com", &config), "to=john%40test.com");
+    }
+
+    #[test]
+    fn test_deny_list_numbers_and_thai_runs() {
+        let config = MaskingConfig {
+            deny_list: std::sync::Arc::new(crate::denylist::DenyList::from_values(["123456", "สมชาย"])),
+            ..Default::default()
+        };
+        let mut value = json!({"customer_id": 123456, "qty": 12, "note": "ลูกค้าสมชายโทรมา"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"customer_id": "[REDACTED]", "qty": 12, "note": "ลูกค้า[REDACTED]โทรมา"}));
+    }
+
+    #[test]
+    fn test_allow_and_deny_lists() {
+        let config = MaskingConfig {
+            allow_values: vec!["4242 4242 4242 4242".to_string()],
+            deny_list: std::sync::Arc::new(crate::denylist::DenyList::from_values(["C77Q-19"])),
+            ..Default::default()
+        };
+        assert_eq!(
+            apply_masking("test card 4242424242424242, real 4532015112830366", &config),
+            "test card 4242424242424242, real 4532********0366"
+        );
+        let mut value = json!({"note": "customer C77Q-19 called", "items": ["C77Q-19"]});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"note": "customer [REDACTED] called", "items": ["[REDACTED]"]}));
+    }
+
+    #[test]
+    fn test_dictionary_terms() {
+        let terms: crate::config::TermsConfig =
+            serde_yaml::from_str("{ values: [Project Nova, บริษัท สยามพาร์ทเนอร์] }").unwrap();
+        let config = MaskingConfig {
+            term_list: Some(std::sync::Arc::new(crate::terms::TermList::new(terms).unwrap())),
+            ..Default::default()
+        };
+        assert_eq!(
+            apply_masking("PROJECT NOVA ส่งให้บริษัท สยามพาร์ทเนอร์ โทร 0812345678", &config),
+            "PR*** ส่งให้บร*** โทร 081XXXXX78"
+        );
+    }
+
+    #[test]
+    fn test_crypto_wallets() {
+        assert_eq!(
+            apply_global_standard_masking("withdraw to 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"),
+            "withdraw to 1A1z**************************vfNa"
+        );
+        assert_eq!(
+            apply_global_standard_masking("from 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            "from 0x5aAe********************************eAed"
+        );
+        // A transaction hash is not an address
+        let tx = "tx 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed5aaeb6053f3e94c9b9a09f33";
+        assert_eq!(apply_global_standard_masking(tx), tx);
+
+        // A valid checksum outweighs any penalising keyword
+        let mut config = MaskingConfig::default();
+        config.scoring.keywords.insert(
+            "bitcoin_address".to_string(),
+            crate::config::KeywordConfig {
+                boost: vec![],
+                penalty: vec!["withdraw".to_string()],
+            },
+        );
+        assert_eq!(
+            apply_masking("withdraw to 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", &config),
+            "withdraw to 1A1z**************************vfNa"
+        );
+
+        // Ethereum: only an EIP-55 mixed-case address has a checksum to outweigh a penalty
+        config.scoring.keywords.insert(
+            "ethereum_address".to_string(),
+            crate::config::KeywordConfig {
+                boost: vec![],
+                penalty: vec!["sample".to_string()],
+            },
+        );
+        assert_eq!(
+            apply_masking("sample wallet 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", &config),
+            "sample wallet 0x5aAe********************************eAed"
+        );
+        let unchecked = "sample wallet 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+        assert_eq!(apply_masking(unchecked, &config), unchecked);
+    }
+
+    #[test]
+    fn test_urls_masked_by_part() {
+        let line = r#"10.0.0.1 - "GET /users/1103700012346/orders?email=john%40test.com&phone=0812345678&page=2 HTTP/1.1" 200"#;
+        assert_eq!(
+            apply_global_standard_masking(line),
+            r#"10.0.0.XXX - "GET /users/110XXXXXX2346/orders?email=jo%2A%2A%2A%40test.com&phone=081XXXXX78&page=2 HTTP/1.1" 200"#
+        );
+
+        // Host and user info go through the detectors; the scoring context spans the whole line
+        assert_eq!(apply_global_standard_masking("fetch http://10.1.2.3/x"), "fetch http://10.1.2.XXX/x");
+        assert_eq!(
+            apply_global_standard_masking("mail smtp://somchai%40test.com@mx.example:25 ok"),
+            "mail smtp://so%2A%2A%2A%40test.com@mx.example:25 ok"
+        );
+        let mut config = MaskingConfig::default();
+        config.scoring.keywords.insert(
+            "phone".to_string(),
+            crate::config::KeywordConfig {
+                boost: vec![],
+                penalty: vec!["order".to_string()],
+            },
+        );
+        let line = "order via https://shop.example/cart 0812345678";
+        assert_eq!(apply_masking(line, &config), line);
+
+        let config: MaskingConfig = serde_yaml::from_str(
+            r#"
+field_rules:
+  - { key: "token", action: redact }
+  - { key: "device", action: keep }
+"#,
+        )
+        .unwrap();
+        // Field rules apply to parameter names; encoded Thai and `+` survive the round trip
+        let url = "https://api.example.com/v1?token=abc123&device=0812345678&q=%E0%B9%82%E0%B8%97%E0%B8%A3+0812345678#top";
+        assert_eq!(
+            apply_masking(url, &config),
+            "https://api.example.com/v1?token=%5BREDACTED%5D&device=0812345678&q=%E0%B9%82%E0%B8%97%E0%B8%A3+081XXXXX78#top"
+        );
+    }
+
     #[test]
     fn test_large_input_safety() {
         let large_input = "a".repeat(10000);
@@ -174,7 +1111,132 @@ mod chaos_tests {
         let config = crate::config::MaskingConfig {
             exclude_fields: vec![],
             max_depth: 20,
+            ..Default::default()
         };
         mask_pii(&mut root, 0, &config);
     }
+
+    #[test]
+    fn test_policy_strategies() {
+        let mut config = MaskingConfig::default();
+        config.strategies.credit_card = MaskStrategy::KeepLast4;
+        config.strategies.name = MaskStrategy::Redact;
+
+        let masked = apply_masking("card 4532-0151-1283-0366", &config);
+        assert_eq!(masked, "card ****-****-****-0366");
+
+        let mut value = json!({"full_name": "สมชาย ใจดี"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value["full_name"], "[REDACTED]");
+    }
+
+    #[test]
+    fn test_thai_and_fullwidth_digits() {
+        let masked = apply_global_standard_masking("เลขบัตร ๑๑๐๓๗๐๐๐๑๒๓๔๖ โทร ๐๘๑๒๓๔๕๖๗๘");
+        assert_eq!(masked, "เลขบัตร ๑๑๐XXXXXX๒๓๔๖ โทร ๐๘๑XXXXX๗๘");
+
+        let masked = apply_global_standard_masking("tel ０８１-２３４-５６７８");
+        assert_eq!(masked, "tel ０８１-XXX-XX７８");
+    }
+
+    #[test]
+    fn test_grouped_and_split_thai_ids() {
+        assert_eq!(apply_global_standard_masking("id 1-1037-00012-34-6"), "id 1-10XX-XXXX2-34-6");
+        assert_eq!(apply_global_standard_masking("id 1 1037 00012 34 6"), "id 1 10XX XXXX2 34 6");
+        assert_eq!(
+            apply_global_standard_masking("id 1103\u{200B}7000\u{200B}12346"),
+            "id 110XXXXXX2346"
+        );
+        // Checksum still gates the grouped layout
+        assert_eq!(apply_global_standard_masking("id 1-1037-00012-34-5"), "id 1-1037-00012-34-5");
+    }
+
+    #[test]
+    fn test_ip_addresses_keep_their_subnet() {
+        let line = "login from 192.168.1.77 via 2001:db8:85a3::8a2e:370:7334";
+        assert_eq!(
+            apply_global_standard_masking(line),
+            "login from 192.168.1.XXX via 2001:db8:85a3:XXXX:XXXX:XXXX:XXXX:XXXX"
+        );
+
+        let mut config = MaskingConfig::default();
+        config.strategies.ip_address = MaskStrategy::Truncate;
+        assert_eq!(apply_masking(line, &config), "login from 192.168.1.0/24 via 2001:db8:85a3::/48");
+        // Version strings stay readable
+        assert_eq!(apply_masking("agent v1.2.3.4", &config), "agent v1.2.3.4");
+    }
+
+    #[test]
+    fn test_bank_account_and_promptpay() {
+        assert_eq!(apply_global_standard_masking("โอนเข้าบัญชี 123-4-56789-0"), "โอนเข้าบัญชี XXX-X-XX789-0");
+        assert_eq!(apply_global_standard_masking("promptpay 1103700012346"), "promptpay 110XXXXXX2346");
+        assert_eq!(apply_global_standard_masking("ref 1234567890"), "ref 1234567890");
+    }
+
+    #[test]
+    fn test_document_numbers_after_keywords() {
+        assert_eq!(
+            apply_global_standard_masking("passport AA1234567, ทะเบียน กข 1234 กรุงเทพมหานคร"),
+            "passport AAXXXXX67, ทะเบียน กข XXXX กรุงเทพมหานคร"
+        );
+        assert_eq!(apply_global_standard_masking("shipment AA1234567"), "shipment AA1234567");
+    }
+
+    #[test]
+    fn test_asean_national_ids() {
+        assert_eq!(
+            apply_global_standard_masking("nric S1234567D mykad 900101-14-5678"),
+            "nric SXXXX567D mykad XXXXXX-XX-5678"
+        );
+        // Bare 12/16-digit runs need their keyword
+        let mut config = MaskingConfig::default();
+        config.detectors.phone = false;
+        assert_eq!(apply_masking("order 079203012345", &config), "order 079203012345");
+        assert_eq!(apply_global_standard_masking("CCCD 079203012345"), "CCCD XXXXXXXX2345");
+    }
+
+    #[test]
+    fn test_iban_and_routing_keep_suffix() {
+        assert_eq!(
+            apply_global_standard_masking("iban GB82 WEST 1234 5698 7654 32"),
+            "iban GB82 **** **** **** **54 32"
+        );
+        assert_eq!(apply_global_standard_masking("routing 021000021"), "routing *****0021");
+    }
+
+    #[test]
+    fn test_card_pci_format_and_details() {
+        let mut config = MaskingConfig::default();
+        config.strategies.credit_card = MaskStrategy::Pci;
+        assert_eq!(
+            apply_masking("card 4532015112830366 exp 12/27 cvv 123", &config),
+            "card 453201******0366 exp **/** cvv ***"
+        );
+        // Luhn-valid but no card network issues it
+        assert_eq!(apply_global_standard_masking("order 1000000000000008"), "order 1000000000000008");
+
+        let mut value = json!({"pan": "4532015112830366", "cvv": "123", "exp": "12/27", "otp": "123"});
+        mask_pii(&mut value, 0, &config);
+        assert_eq!(value, json!({"pan": "453201******0366", "cvv": "***", "exp": "***", "otp": "123"}));
+    }
+
+    #[test]
+    fn test_card_split_by_zero_width_space() {
+        let masked = apply_global_standard_masking("card 4532\u{200B}0151\u{200B}1283\u{200B}0366");
+        assert_eq!(masked, "card 4532********0366");
+    }
+
+    #[test]
+    fn test_mask_payload_respects_exclude_fields() {
+        let config = MaskingConfig {
+            exclude_fields: vec!["customer_id".to_string()],
+            ..Default::default()
+        };
+        let input = "{\"customer_id\":\"1103700012346\",\"phone\":\"0812345678\"}\nplain 0812345678\n";
+        let masked = mask_payload(input, &config);
+        assert_eq!(
+            masked,
+            "{\"customer_id\":\"1103700012346\",\"phone\":\"081XXXXX78\"}\nplain 081XXXXX78\n"
+        );
+    }
 }
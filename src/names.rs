@@ -0,0 +1,158 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+use std::sync::Arc;
+use std::{fs, io};
+
+/// Bundled list of common Thai given names and surnames, in Thai script and romanized.
+const BUNDLED_NAMES: &str = include_str!("../data/thai_names.txt");
+
+lazy_static! {
+    // Thai honorifics; the name follows directly or after one space (คุณสมชาย, นาย สมชาย)
+    static ref RE_THAI_HONORIFIC: Regex = Regex::new(r"(?:นางสาว|นาง|นาย|คุณ|น\.ส\.|ด\.ช\.|ด\.ญ\.) ?").unwrap();
+
+    // Latin honorifics: Mr. Somchai, Ms Jaidee, Khun Somchai
+    static ref RE_LATIN_HONORIFIC: Regex = Regex::new(r"\b(?:Mr|Mrs|Ms|Miss|Khun)\.? +").unwrap();
+
+    static ref RE_LATIN_WORD: Regex = Regex::new(r"^[A-Za-z][A-Za-z'-]*").unwrap();
+
+    static ref BUNDLED: Arc<NameDictionary> = Arc::new(NameDictionary::from_list(BUNDLED_NAMES));
+}
+
+/// Given names and surnames used to find where a name ends in unspaced Thai text.
+#[derive(Debug, Clone, Default)]
+pub struct NameDictionary {
+    names: HashSet<String>,
+    /// Longest entry in chars; bounds the prefix search.
+    max_chars: usize,
+}
+
+/// Thai vowel and tone marks that attach to the previous consonant; a name never ends right before one.
+pub(crate) fn is_thai_mark(c: char) -> bool {
+    matches!(c, '\u{0E30}'..='\u{0E3A}' | '\u{0E45}' | '\u{0E47}'..='\u{0E4E}')
+}
+
+impl NameDictionary {
+    pub fn bundled() -> Arc<Self> {
+        BUNDLED.clone()
+    }
+
+    /// The bundled names plus those in the file at `path`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut dictionary = NameDictionary::clone(&BUNDLED);
+        dictionary.extend(&fs::read_to_string(path)?);
+        Ok(dictionary)
+    }
+
+    /// One name per line; blank lines and `#` comments are skipped. Matching ignores case.
+    pub fn from_list(list: &str) -> Self {
+        let mut dictionary = Self::default();
+        dictionary.extend(list);
+        dictionary
+    }
+
+    pub fn extend(&mut self, list: &str) {
+        for line in list.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            self.max_chars = self.max_chars.max(line.chars().count());
+            self.names.insert(line.to_lowercase());
+        }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.names.contains(&word.to_lowercase())
+    }
+
+    /// Longest entry that `text` starts with and that ends on a Thai character-cluster boundary.
+    fn longest_prefix(&self, text: &str) -> Option<usize> {
+        let ends: Vec<usize> = text
+            .char_indices()
+            .skip(1)
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .take(self.max_chars)
+            .collect();
+        ends.into_iter().rev().find(|&end| {
+            let at_boundary = !text[end..].starts_with(is_thai_mark);
+            at_boundary && self.names.contains(&text[..end].to_lowercase())
+        })
+    }
+
+    /// Byte ranges of names after an honorific: the given name, then a surname if one follows.
+    pub fn find_names(&self, text: &str) -> Vec<Range<usize>> {
+        let mut found: Vec<Range<usize>> = Vec::new();
+
+        for honorific in RE_THAI_HONORIFIC.find_iter(text) {
+            let start = honorific.end();
+            let Some(len) = self.longest_prefix(&text[start..]) else { continue };
+            found.push(start..start + len);
+            // Surname: one space, then another dictionary entry
+            let surname = start + len + 1;
+            if text[start + len..].starts_with(' ')
+                && let Some(len) = self.longest_prefix(&text[surname..])
+            {
+                found.push(surname..surname + len);
+            }
+        }
+
+        for honorific in RE_LATIN_HONORIFIC.find_iter(text) {
+            let mut start = honorific.end();
+            // Given name and surname: capitalised or known words, at most two
+            for _ in 0..2 {
+                let Some(word) = RE_LATIN_WORD.find(&text[start..]) else { break };
+                let word = word.as_str();
+                if !(word.starts_with(|c: char| c.is_ascii_uppercase()) || self.contains(word)) {
+                    break;
+                }
+                found.push(start..start + word.len());
+                match text[start + word.len()..].strip_prefix(' ') {
+                    Some(_) => start += word.len() + 1,
+                    None => break,
+                }
+            }
+        }
+
+        found.sort_by_key(|r| r.start);
+        found.dedup_by(|b, a| a.end > b.start);
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(text: &str) -> Vec<&str> {
+        NameDictionary::bundled()
+            .find_names(text)
+            .into_iter()
+            .map(|r| &text[r])
+            .collect()
+    }
+
+    #[test]
+    fn test_thai_names_without_spaces() {
+        assert_eq!(names("ติดต่อคุณสมชายที่เบอร์"), vec!["สมชาย"]);
+        assert_eq!(names("นางสาวสมหญิง ใจดี โทรมา"), vec!["สมหญิง", "ใจดี"]);
+        // คุณภาพ: "คุณ" here is not an honorific, ภาพ is no name
+        assert!(names("คุณภาพดี").is_empty());
+    }
+
+    #[test]
+    fn test_cluster_boundary() {
+        let dictionary = NameDictionary::from_list("สม");
+        // สมา... would cut a vowel off its consonant
+        assert!(dictionary.find_names("คุณสมาน").is_empty());
+        assert_eq!(dictionary.find_names("คุณสม มา"), vec![9..15]);
+    }
+
+    #[test]
+    fn test_romanized_names() {
+        assert_eq!(names("Mr. Somchai Jaidee called"), vec!["Somchai", "Jaidee"]);
+        assert_eq!(names("khun ok, Khun somchai here"), vec!["somchai"]);
+    }
+}
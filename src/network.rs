@@ -0,0 +1,227 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::net::{Ipv4Addr, Ipv6Addr};
+use std::ops::Range;
+
+lazy_static! {
+    // Dotted quad candidate, confirmed by Ipv4Addr parsing (no octet > 255, no leading zeros)
+    static ref RE_IPV4: Regex = Regex::new(r"[0-9]{1,3}(?:\.[0-9]{1,3}){3}").unwrap();
+
+    // IPv6 candidate: hex groups and colons, optionally ending in an embedded IPv4
+    // Example: 2001:db8:85a3::8a2e:370:7334, fe80::1, ::ffff:192.0.2.1
+    static ref RE_IPV6: Regex = Regex::new(
+        r"(?i)(?:[0-9a-f]{1,4}|:)?(?::[0-9a-f]{0,4}){2,7}(?:(?:\.[0-9]{1,3}){3}|[0-9a-f]{0,4})"
+    ).unwrap();
+
+    // MAC: 00:1A:2B:3C:4D:5E, 00-1A-2B-3C-4D-5E, Cisco 001a.2b3c.4d5e
+    static ref RE_MAC: Regex = Regex::new(
+        r"(?i)[0-9a-f]{2}(?::[0-9a-f]{2}){5}|[0-9a-f]{2}(?:-[0-9a-f]{2}){5}|[0-9a-f]{4}\.[0-9a-f]{4}\.[0-9a-f]{4}"
+    ).unwrap();
+
+    // Text right before a dotted quad that makes it a version number: "v1.2.3.4", "version: 1.2.3.4", "Agent/1.2.3.4"
+    static ref RE_VERSION_CONTEXT: Regex =
+        Regex::new(r"(?i)(?:\bv|\bver|\bversion|[a-z]/)\s*[:=]?\s*$").unwrap();
+}
+
+/// Hex digits of a MAC kept by the partial mask: the vendor prefix (OUI).
+const MAC_KEEP_HEX: usize = 6;
+
+/// Bytes of context looked at before a candidate for version keywords.
+const VERSION_LOOKBACK: usize = 16;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum NetworkKind {
+    Ipv4(Ipv4Addr),
+    Ipv6(Ipv6Addr),
+    Mac,
+}
+
+impl NetworkKind {
+    /// Detector that finds this kind, as named in the config.
+    pub fn detector(&self) -> &'static str {
+        match self {
+            NetworkKind::Ipv4(_) | NetworkKind::Ipv6(_) => "ip_address",
+            NetworkKind::Mac => "mac_address",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct NetworkMatch {
+    pub range: Range<usize>,
+    pub kind: NetworkKind,
+}
+
+fn is_version_context(text: &str, start: usize, end: usize) -> bool {
+    let mut from = start.saturating_sub(VERSION_LOOKBACK);
+    while !text.is_char_boundary(from) {
+        from += 1;
+    }
+    // Pre-release or build suffix: 1.2.3.4-beta, 1.2.3.4+build
+    let suffix = text[end..].starts_with(['-', '+']);
+    suffix || RE_VERSION_CONTEXT.is_match(&text[from..start])
+}
+
+/// Finds IP and MAC addresses in `text`, earliest first and never overlapping.
+pub fn find_network(text: &str, ip: bool, mac: bool) -> Vec<NetworkMatch> {
+    let bytes = text.as_bytes();
+    let before = |i: usize| (i > 0).then(|| bytes[i - 1]);
+    let after = |i: usize| bytes.get(i).copied();
+    let mut found: Vec<NetworkMatch> = Vec::new();
+
+    if mac {
+        for mat in RE_MAC.find_iter(text) {
+            let glued = before(mat.start()).is_some_and(|b| b.is_ascii_alphanumeric() || b == b':' || b == b'-')
+                || after(mat.end()).is_some_and(|b| b.is_ascii_alphanumeric() || b == b':' || b == b'-');
+            if !glued {
+                found.push(NetworkMatch { range: mat.range(), kind: NetworkKind::Mac });
+            }
+        }
+    }
+
+    if ip {
+        for mat in RE_IPV6.find_iter(text) {
+            // "fe80::1:" at the end of "addr=fe80::1: ..." - the last colon is punctuation
+            let candidate = match mat.as_str().strip_suffix(':') {
+                Some(rest) if !rest.ends_with(':') => rest,
+                _ => mat.as_str(),
+            };
+            let range = mat.start()..mat.start() + candidate.len();
+            let glued = before(range.start).is_some_and(|b| b.is_ascii_alphanumeric() || b == b'.')
+                || after(range.end).is_some_and(|b| b.is_ascii_alphanumeric());
+            // "::" on its own (Rust/C++ paths) is not an address
+            let has_digit = candidate.chars().any(|c| c.is_ascii_hexdigit());
+            if let Ok(addr) = candidate.parse::<Ipv6Addr>()
+                && !glued
+                && has_digit
+            {
+                found.push(NetworkMatch { range, kind: NetworkKind::Ipv6(addr) });
+            }
+        }
+
+        for mat in RE_IPV4.find_iter(text) {
+            // Part of a longer dotted run (OID, 5-part version) or glued to a word
+            let glued = before(mat.start()).is_some_and(|b| b.is_ascii_alphanumeric() || b == b'.')
+                || after(mat.end()).is_some_and(|b| b.is_ascii_alphanumeric())
+                || text[mat.end()..].starts_with('.')
+                    && text[mat.end() + 1..].starts_with(|c: char| c.is_ascii_digit());
+            if glued || is_version_context(text, mat.start(), mat.end()) {
+                continue;
+            }
+            if let Ok(addr) = mat.as_str().parse::<Ipv4Addr>() {
+                found.push(NetworkMatch { range: mat.range(), kind: NetworkKind::Ipv4(addr) });
+            }
+        }
+    }
+
+    // Earlier detectors win overlaps (an IPv4 embedded in an IPv6 belongs to the IPv6)
+    let mut kept: Vec<NetworkMatch> = Vec::new();
+    for m in found {
+        if !kept.iter().any(|k| k.range.start < m.range.end && m.range.start < k.range.end) {
+            kept.push(m);
+        }
+    }
+    kept.sort_by_key(|m| m.range.start);
+    kept
+}
+
+/// Hides the octets past `prefix`: 192.168.1.77 with /24 -> 192.168.1.XXX
+pub fn mask_ipv4(addr: Ipv4Addr, prefix: u8) -> String {
+    let keep = usize::from(prefix / 8);
+    addr.octets()
+        .iter()
+        .enumerate()
+        .map(|(i, o)| if i < keep { o.to_string() } else { "XXX".to_string() })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Network address in CIDR form: 192.168.1.77 with /24 -> 192.168.1.0/24
+pub fn truncate_ipv4(addr: Ipv4Addr, prefix: u8) -> String {
+    let mask = u32::MAX.checked_shl(32 - u32::from(prefix.min(32))).unwrap_or(0);
+    format!("{}/{}", Ipv4Addr::from(u32::from(addr) & mask), prefix)
+}
+
+/// Hides the hextets past `prefix`: 2001:db8:85a3::7334 with /48 -> 2001:db8:85a3:XXXX:XXXX:XXXX:XXXX:XXXX
+pub fn mask_ipv6(addr: Ipv6Addr, prefix: u8) -> String {
+    let keep = usize::from(prefix / 16);
+    addr.segments()
+        .iter()
+        .enumerate()
+        .map(|(i, s)| if i < keep { format!("{:x}", s) } else { "XXXX".to_string() })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Network address in CIDR form: 2001:db8:85a3::7334 with /48 -> 2001:db8:85a3::/48
+pub fn truncate_ipv6(addr: Ipv6Addr, prefix: u8) -> String {
+    let mask = u128::MAX.checked_shl(128 - u32::from(prefix.min(128))).unwrap_or(0);
+    format!("{}/{}", Ipv6Addr::from(u128::from(addr) & mask), prefix)
+}
+
+/// Keeps the vendor prefix and separators: 00:1A:2B:3C:4D:5E -> 00:1A:2B:XX:XX:XX
+pub fn mask_mac(mac: &str) -> String {
+    let mut seen = 0;
+    mac.chars()
+        .map(|c| {
+            if !c.is_ascii_hexdigit() {
+                return c;
+            }
+            seen += 1;
+            if seen <= MAC_KEEP_HEX { c } else { 'X' }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<&str> {
+        find_network(text, true, true)
+            .iter()
+            .map(|m| &text[m.range.clone()])
+            .collect()
+    }
+
+    #[test]
+    fn test_addresses_are_found() {
+        assert_eq!(found("from 10.0.0.7 to 192.168.1.77"), vec!["10.0.0.7", "192.168.1.77"]);
+        assert_eq!(found("src=2001:db8:85a3::8a2e:370:7334 ok"), vec!["2001:db8:85a3::8a2e:370:7334"]);
+        assert_eq!(found("peer [fe80::1]:443"), vec!["fe80::1"]);
+        assert_eq!(found("mapped ::ffff:192.0.2.1"), vec!["::ffff:192.0.2.1"]);
+        assert_eq!(found("addr=fe80::1: up"), vec!["fe80::1"]);
+        assert_eq!(found("hw 00:1A:2B:3C:4D:5E / 001a.2b3c.4d5e"), vec!["00:1A:2B:3C:4D:5E", "001a.2b3c.4d5e"]);
+    }
+
+    #[test]
+    fn test_versions_and_lookalikes_are_ignored() {
+        for text in [
+            "v1.2.3.4",
+            "version: 1.2.3.4",
+            "Agent/1.2.3.4",
+            "release 1.2.3.4-beta",
+            "oid 1.3.6.1.4.1",
+            "999.1.1.1",
+            "at 10:30:00",
+            "std::vector",
+            "2025-02-13T10:30:00Z",
+        ] {
+            assert!(found(text).is_empty(), "{}", text);
+        }
+    }
+
+    #[test]
+    fn test_prefix_masks() {
+        let v4: Ipv4Addr = "192.168.1.77".parse().unwrap();
+        assert_eq!(mask_ipv4(v4, 24), "192.168.1.XXX");
+        assert_eq!(truncate_ipv4(v4, 24), "192.168.1.0/24");
+        assert_eq!(truncate_ipv4(v4, 20), "192.168.0.0/20");
+
+        let v6: Ipv6Addr = "2001:db8:85a3::8a2e:370:7334".parse().unwrap();
+        assert_eq!(mask_ipv6(v6, 48), "2001:db8:85a3:XXXX:XXXX:XXXX:XXXX:XXXX");
+        assert_eq!(truncate_ipv6(v6, 48), "2001:db8:85a3::/48");
+
+        assert_eq!(mask_mac("00-1A-2B-3C-4D-5E"), "00-1A-2B-XX-XX-XX");
+    }
+}
@@ -0,0 +1,167 @@
+use std::ops::Range;
+
+/// Zero code point of each Unicode decimal digit block we fold to ASCII.
+const DIGIT_ZEROS: &[u32] = &[
+    0x0660, // Arabic-Indic
+    0x06F0, // Extended Arabic-Indic
+    0x0966, // Devanagari
+    0x09E6, // Bengali
+    0x0BE6, // Tamil
+    0x0E50, // Thai ๐-๙
+    0x0ED0, // Lao
+    0x1040, // Myanmar
+    0x17E0, // Khmer
+    0xFF10, // Full-width ０-９
+];
+
+/// Invisible characters used to split values so they slip past detectors.
+const INVISIBLE: &[char] = &[
+    '\u{00AD}', // soft hyphen
+    '\u{180E}', // Mongolian vowel separator
+    '\u{200B}', // zero-width space
+    '\u{200C}', // zero-width non-joiner
+    '\u{200D}', // zero-width joiner
+    '\u{2060}', // word joiner
+    '\u{FEFF}', // zero-width no-break space (BOM)
+];
+
+pub fn is_invisible(c: char) -> bool {
+    INVISIBLE.contains(&c)
+}
+
+/// Bytes before a detected value searched for a context keyword.
+const CONTEXT_LOOKBACK: usize = 48;
+
+/// Up to `CONTEXT_LOOKBACK` bytes of `text` ending at `start`, cut on a character boundary.
+pub(crate) fn context_before(text: &str, start: usize) -> &str {
+    let mut from = start.saturating_sub(CONTEXT_LOOKBACK);
+    while !text.is_char_boundary(from) {
+        from += 1;
+    }
+    &text[from..start]
+}
+
+/// Returns the digit value and the zero of its block for a non-ASCII decimal digit.
+pub fn unicode_digit(c: char) -> Option<(u32, u32)> {
+    let cp = c as u32;
+    DIGIT_ZEROS
+        .iter()
+        .find(|&&zero| (zero..zero + 10).contains(&cp))
+        .map(|&zero| (cp - zero, zero))
+}
+
+/// Text with every Unicode decimal digit replaced by its ASCII digit and invisible characters
+/// removed, for detection only. Keeps a byte offset map so matches can be applied to the original text.
+pub struct NormalizedText {
+    pub text: String,
+    /// `offsets[i]` is the original byte offset of normalized byte `i` (one extra entry for the end).
+    /// `None` when the input had nothing to fold and offsets are identical.
+    offsets: Option<Vec<usize>>,
+}
+
+impl NormalizedText {
+    pub fn new(input: &str) -> Self {
+        // Folded digits and invisible characters are all outside ASCII
+        if input.is_ascii() || !input.chars().any(|c| unicode_digit(c).is_some() || is_invisible(c)) {
+            return Self {
+                text: input.to_string(),
+                offsets: None,
+            };
+        }
+
+        let mut text = String::with_capacity(input.len());
+        let mut offsets = Vec::with_capacity(input.len() + 1);
+        for (i, c) in input.char_indices() {
+            if is_invisible(c) {
+                continue;
+            }
+            match unicode_digit(c) {
+                Some((value, _)) => {
+                    text.push(char::from(b'0' + value as u8));
+                    offsets.push(i);
+                }
+                None => {
+                    text.push(c);
+                    offsets.extend(std::iter::repeat_n(i, c.len_utf8()));
+                }
+            }
+        }
+        offsets.push(input.len());
+
+        Self {
+            text,
+            offsets: Some(offsets),
+        }
+    }
+
+    pub fn original_range(&self, range: Range<usize>) -> Range<usize> {
+        match &self.offsets {
+            Some(offsets) => offsets[range.start]..offsets[range.end],
+            None => range,
+        }
+    }
+
+    /// Replaces detected spans (normalized byte ranges, ascending, non-overlapping) in `original`.
+    /// Digits the mask keeps are written back in the script they had in the original.
+    pub fn splice(&self, original: &str, spans: Vec<(Range<usize>, String)>) -> String {
+        if spans.is_empty() {
+            return original.to_string();
+        }
+
+        let mut result = String::with_capacity(original.len());
+        let mut last = 0;
+        for (range, masked) in spans {
+            let range = self.original_range(range);
+            result.push_str(&original[last..range.start]);
+            result.push_str(&restore_script(&masked, &original[range.clone()]));
+            last = range.end;
+        }
+        result.push_str(&original[last..]);
+        result
+    }
+}
+
+/// Re-encodes the ASCII digits of `masked` in the digit script used by `original`.
+pub fn restore_script(masked: &str, original: &str) -> String {
+    let Some(zero) = original.chars().find_map(|c| unicode_digit(c).map(|(_, zero)| zero)) else {
+        return masked.to_string();
+    };
+    masked
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(d) if c.is_ascii_digit() => char::from_u32(zero + d).unwrap_or(c),
+            _ => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thai_and_fullwidth_digits_fold_to_ascii() {
+        assert_eq!(NormalizedText::new("โทร ๐๘๑").text, "โทร 081");
+        assert_eq!(NormalizedText::new("０８１").text, "081");
+    }
+
+    #[test]
+    fn test_invisible_characters_are_dropped() {
+        let original = "id 110\u{200B}37\u{200D}00012346 ok";
+        let normalized = NormalizedText::new(original);
+        assert_eq!(normalized.text, "id 1103700012346 ok");
+
+        let start = normalized.text.find('1').unwrap();
+        let masked = normalized.splice(original, vec![(start..start + 13, "110XXXXXX2346".to_string())]);
+        assert_eq!(masked, "id 110XXXXXX2346 ok");
+    }
+
+    #[test]
+    fn test_splice_keeps_original_script() {
+        let original = "ID ๑๑๐๓ end";
+        let normalized = NormalizedText::new(original);
+        let start = normalized.text.find("1103").unwrap();
+        let masked = normalized.splice(original, vec![(start..start + 4, "11XX".to_string())]);
+        assert_eq!(masked, "ID ๑๑XX end");
+    }
+}
@@ -0,0 +1,256 @@
+use crate::normalize::context_before;
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::cell::OnceCell;
+
+lazy_static! {
+    // Phone candidate: optional "+" or "(", then 7+ digits separated by up to 3 of space . - ( )
+    // Example: +66 81 234 5678, 0066 81 234 5678, (02) 123-4567, 081.234.5678
+    // Unbounded on purpose: a run of digits is always taken whole, then split into numbers.
+    static ref RE_PHONE_CANDIDATE: Regex = Regex::new(r"[+(]?[0-9](?:[ .\-()]{0,3}[0-9]){6,}").unwrap();
+
+    // A country code written without "+" or "00" (tel 66812345678) only counts after one of these
+    static ref RE_PHONE_CONTEXT: Regex =
+        Regex::new(r"(?i)(phone|\btel\b|mobile|\bcell|whatsapp|โทร|เบอร์|มือถือ)").unwrap();
+}
+
+/// National significant number (NSN) rules for one country: allowed first digits and length range.
+struct NumberingPlan {
+    country_code: &'static str,
+    /// Trunk prefix "0" is used in national format (0 + NSN)
+    national_trunk: bool,
+    rules: &'static [(&'static str, usize, usize)],
+}
+
+// Longest country codes first so "856" wins over a hypothetical "85"
+const PLANS: &[NumberingPlan] = &[
+    // Laos: mobile 20 + 8 digits, 30 + 7 digits; landline 2x + 6-7 digits
+    NumberingPlan { country_code: "856", national_trunk: true, rules: &[("2", 8, 10), ("3", 9, 9)] },
+    // Cambodia
+    NumberingPlan { country_code: "855", national_trunk: false, rules: &[("123456789", 8, 9)] },
+    // Thailand: mobile 6/8/9 + 8 digits; landline 2-7 + 7 digits
+    NumberingPlan { country_code: "66", national_trunk: true, rules: &[("689", 9, 9), ("234567", 8, 8)] },
+    // Malaysia: mobile 1x + 7-8 digits; Kuala Lumpur 3 + 8 digits; other landlines 8 digits
+    NumberingPlan { country_code: "60", national_trunk: true, rules: &[("1", 9, 10), ("3", 9, 9), ("456789", 8, 8)] },
+    // Singapore (no trunk prefix)
+    NumberingPlan { country_code: "65", national_trunk: false, rules: &[("689", 8, 8)] },
+    // Vietnam: mobile 3/5/7/8/9 + 8 digits, landline 2x + 8 digits; nationally 0 + 9-10 digits
+    NumberingPlan { country_code: "84", national_trunk: true, rules: &[("35789", 9, 9), ("2", 10, 10)] },
+    // Indonesia: mobile 8xx + 6-9 digits, landline area code + number; nationally with a leading 0
+    NumberingPlan { country_code: "62", national_trunk: true, rules: &[("8", 9, 12), ("234567", 8, 11)] },
+];
+
+/// Longest span (in bytes) a single formatted number can take: 15 digits plus separators.
+const MAX_PHONE_BYTES: usize = 32;
+
+/// Digits kept at the end of a masked number.
+const KEEP_SUFFIX_DIGITS: usize = 2;
+
+/// A phone number found in text: byte range and how many leading digits the partial mask keeps.
+#[derive(Debug, PartialEq, Eq)]
+pub struct PhoneMatch {
+    pub start: usize,
+    pub end: usize,
+    pub keep_prefix_digits: usize,
+}
+
+impl NumberingPlan {
+    fn accepts(&self, nsn: &str) -> bool {
+        let Some(first) = nsn.chars().next() else {
+            return false;
+        };
+        self.rules
+            .iter()
+            .any(|(firsts, min, max)| firsts.contains(first) && (*min..=*max).contains(&nsn.len()))
+    }
+}
+
+/// Leading digits worth keeping from a valid NSN: the area/operator digits.
+fn nsn_keep(nsn: &str) -> usize {
+    if nsn.len() >= 9 { 2 } else { 1 }
+}
+
+/// Validates a single candidate against the numbering plans; returns the digits to keep on success.
+/// A country code needs a "+" or "00" in front unless a phone keyword (`labelled`) precedes it.
+fn classify(candidate: &str, labelled: impl Fn() -> bool) -> Option<usize> {
+    let digits: String = candidate.chars().filter(|c| c.is_ascii_digit()).collect();
+    let plus = candidate.trim_start_matches('(').starts_with('+');
+    let exit_code = !plus && digits.starts_with("00");
+    let parens = candidate.chars().filter(|&c| c == '(').count();
+    if parens != candidate.chars().filter(|&c| c == ')').count() || parens > 1 {
+        return None;
+    }
+
+    if plus || exit_code {
+        let skip = if exit_code { 2 } else { 0 };
+        let digits = &digits[skip..];
+        for plan in PLANS {
+            if let Some(rest) = digits.strip_prefix(plan.country_code) {
+                // "+66 (0)81 ..." style: drop the trunk digit inside the international form
+                let nsn = rest.strip_prefix('0').filter(|_| plan.national_trunk).unwrap_or(rest);
+                let trunk = rest.len() - nsn.len();
+                return plan
+                    .accepts(nsn)
+                    .then(|| skip + plan.country_code.len() + trunk + nsn_keep(nsn));
+            }
+        }
+        // Any other country: plain E.164 length check
+        return (8..=15).contains(&digits.len()).then_some(skip + 3);
+    }
+
+    if let Some(nsn) = digits.strip_prefix('0') {
+        return PLANS
+            .iter()
+            .filter(|plan| plan.national_trunk)
+            .any(|plan| plan.accepts(nsn))
+            .then(|| 1 + nsn_keep(nsn));
+    }
+
+    // Country code written without "+", e.g. tel 66812345678
+    PLANS
+        .iter()
+        .find_map(|plan| {
+            let nsn = digits.strip_prefix(plan.country_code)?;
+            plan.accepts(nsn).then(|| plan.country_code.len() + nsn_keep(nsn))
+        })
+        .filter(|_| labelled())
+}
+
+/// Finds phone numbers in `text`. A candidate that holds several numbers separated by spaces
+/// is split at digit-group boundaries, taking the longest valid number each time.
+/// Only group ends within `MAX_PHONE_BYTES` of a start are tried, so long digit runs stay linear.
+pub fn find_phones(text: &str) -> Vec<PhoneMatch> {
+    let bytes = text.as_bytes();
+    let mut found = Vec::new();
+
+    for candidate in RE_PHONE_CANDIDATE.find_iter(text) {
+        // Part of a longer digit run (e.g. an ID or hash): not a phone number
+        let digit_before = candidate.start() > 0 && bytes[candidate.start() - 1].is_ascii_digit();
+        let digit_after = bytes.get(candidate.end()).is_some_and(|b| b.is_ascii_digit());
+        if digit_before || digit_after {
+            continue;
+        }
+
+        let slice = candidate.as_str();
+        let group_ends: Vec<usize> = slice
+            .char_indices()
+            .filter(|&(i, c)| c.is_ascii_digit() && !slice[i + 1..].starts_with(|n: char| n.is_ascii_digit()))
+            .map(|(i, _)| i + 1)
+            .collect();
+        let mut group_starts: Vec<usize> = vec![0];
+        group_starts.extend(
+            slice
+                .char_indices()
+                .filter(|&(i, c)| (c == '+' || c == '(' || c.is_ascii_digit()) && i > 0)
+                .filter(|&(i, _)| !slice[..i].ends_with(|p: char| p.is_ascii_digit() || p == '+' || p == '('))
+                .map(|(i, _)| i),
+        );
+
+        let mut cursor = 0;
+        for &start in &group_starts {
+            if start < cursor {
+                continue;
+            }
+            let keyword = OnceCell::new();
+            let labelled = || {
+                *keyword.get_or_init(|| RE_PHONE_CONTEXT.is_match(context_before(text, candidate.start() + start)))
+            };
+            let first = group_ends.partition_point(|&end| end <= start);
+            let last = group_ends.partition_point(|&end| end <= start + MAX_PHONE_BYTES);
+            let matched = group_ends[first..last]
+                .iter()
+                .rev()
+                .find_map(|&end| classify(&slice[start..end], labelled).map(|keep| (end, keep)));
+
+            if let Some((end, keep)) = matched {
+                found.push(PhoneMatch {
+                    start: candidate.start() + start,
+                    end: candidate.start() + end,
+                    keep_prefix_digits: keep,
+                });
+                cursor = end;
+            }
+        }
+    }
+
+    found
+}
+
+/// Replaces the middle digits with X and leaves every separator where it was.
+/// +66 81 234 5678 -> +66 81 XXX XX78, 081-234-5678 -> 081-XXX-XX78
+pub fn mask_phone(phone: &str, keep_prefix_digits: usize) -> String {
+    crate::masker::mask_digits(phone, keep_prefix_digits, KEEP_SUFFIX_DIGITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mask_all(text: &str) -> String {
+        let mut result = text.to_string();
+        for m in find_phones(text).iter().rev() {
+            let masked = mask_phone(&text[m.start..m.end], m.keep_prefix_digits);
+            result.replace_range(m.start..m.end, &masked);
+        }
+        result
+    }
+
+    #[test]
+    fn test_thai_formats() {
+        assert_eq!(mask_all("call 0812345678"), "call 081XXXXX78");
+        assert_eq!(mask_all("call 081-234-5678"), "call 081-XXX-XX78");
+        assert_eq!(mask_all("+66 81 234 5678"), "+66 81 XXX XX78");
+        assert_eq!(mask_all("tel 66812345678"), "tel 6681XXXXX78");
+        assert_eq!(mask_all("0066 81 234 5678"), "0066 81 XXX XX78");
+        assert_eq!(mask_all("(02) 123-4567"), "(02) XXX-XX67");
+        assert_eq!(mask_all("081.234.5678"), "081.XXX.XX78");
+    }
+
+    #[test]
+    fn test_lao_and_malaysian_numbers() {
+        assert_eq!(mask_all("+856 20 5555 1234"), "+856 20 XXXX XX34");
+        assert_eq!(mask_all("+60 12-345 6789"), "+60 12-XXX XX89");
+        assert_eq!(mask_all("012-345 6789"), "012-XXX XX89");
+    }
+
+    #[test]
+    fn test_vietnamese_and_indonesian_numbers() {
+        assert_eq!(mask_all("+84 912 345 678"), "+84 91X XXX X78");
+        assert_eq!(mask_all("0912 345 678"), "091X XXX X78");
+        assert_eq!(mask_all("024 3825 1234"), "024 XXXX XX34");
+        assert_eq!(mask_all("+62 812-3456-7890"), "+62 81X-XXXX-XX90");
+        assert_eq!(mask_all("0812-3456-7890"), "081X-XXXX-XX90");
+    }
+
+    #[test]
+    fn test_adjacent_numbers_are_split() {
+        assert_eq!(mask_all("0812345678 0898765432"), "081XXXXX78 089XXXXX32");
+    }
+
+    #[test]
+    fn test_long_digit_group_runs_stay_linear() {
+        for unit in ["1 ", "1."] {
+            let text = unit.repeat(25_000);
+            let started = std::time::Instant::now();
+            assert!(find_phones(&text).is_empty());
+            assert!(started.elapsed() < std::time::Duration::from_secs(2), "{:?}", started.elapsed());
+        }
+    }
+
+    #[test]
+    fn test_non_phone_numbers_untouched() {
+        for text in [
+            "2025-02-13T10:30:00Z",
+            "192.168.1.100",
+            "order 12345678",
+            "hash 08123456789012345678901",
+            "+66 81 234",
+            // Country code without "+", "00" or a phone keyword
+            "order 66812345678",
+            "ref 84912345678",
+            "id 628123456789",
+        ] {
+            assert_eq!(mask_all(text), text);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+use crate::config::{AppConfig, MaskingConfig};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::warn;
+
+pub const POLICY_HEADER: &str = "x-iron-mask-policy";
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// Name reported for the `masking` section when it acts as the default policy.
+pub const DEFAULT_POLICY_NAME: &str = "default";
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PolicyError {
+    UnknownPolicy(String),
+    /// The header named a policy the caller's API key or identity may not use.
+    NotAllowed(String),
+}
+
+impl std::fmt::Display for PolicyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolicyError::UnknownPolicy(name) => write!(f, "Unknown masking policy: {}", name),
+            PolicyError::NotAllowed(name) => write!(f, "Masking policy not allowed for this client: {}", name),
+        }
+    }
+}
+
+impl std::error::Error for PolicyError {}
+
+/// What a request offers for choosing a policy.
+#[derive(Debug, Default)]
+pub struct PolicyRequest<'a> {
+    pub header: Option<&'a str>,
+    pub api_key: Option<&'a str>,
+    pub identity: Option<&'a str>,
+    pub route: Option<&'a str>,
+}
+
+/// Compiled policies, shared across requests.
+pub struct PolicySet {
+    policies: HashMap<String, Arc<MaskingConfig>>,
+    default_name: String,
+    default: Arc<MaskingConfig>,
+    api_keys: HashMap<String, String>,
+    identities: HashMap<String, String>,
+    header_policies: HashMap<String, Vec<String>>,
+    reject_unknown: bool,
+}
+
+impl PolicySet {
+    pub fn from_config(config: &AppConfig) -> Self {
+        let policies: HashMap<String, Arc<MaskingConfig>> = config
+            .policies
+            .definitions
+            .iter()
+            .map(|(name, policy)| (name.clone(), Arc::new(policy.clone())))
+            .collect();
+
+        let (default_name, default) = match &config.policies.default {
+            Some(name) if policies.contains_key(name) => (name.clone(), Arc::clone(&policies[name])),
+            _ => (DEFAULT_POLICY_NAME.to_string(), Arc::new(config.masking.clone())),
+        };
+
+        Self {
+            policies,
+            default_name,
+            default,
+            api_keys: config.policies.api_keys.clone(),
+            identities: config.policies.identities.clone(),
+            header_policies: config.policies.header_policies.clone(),
+            reject_unknown: config.policies.reject_unknown,
+        }
+    }
+
+    /// Picks the policy for a request. A mapped API key, then client identity, decides first; the
+    /// header may only switch it to a policy listed for that key or identity in `header_policies`.
+    /// Unmapped callers get the header, then the route; otherwise the default applies.
+    pub fn select(&self, request: &PolicyRequest) -> Result<(String, Arc<MaskingConfig>), PolicyError> {
+        let mapped = request
+            .api_key
+            .and_then(|k| self.api_keys.get(k).map(|policy| (k, policy)))
+            .or_else(|| request.identity.and_then(|i| self.identities.get(i).map(|policy| (i, policy))));
+
+        let requested = match (mapped, request.header) {
+            (Some((client, policy)), Some(header)) if header != policy => {
+                let allowed = self
+                    .header_policies
+                    .get(client)
+                    .is_some_and(|names| names.iter().any(|name| name == header));
+                if !allowed {
+                    return Err(PolicyError::NotAllowed(header.to_string()));
+                }
+                Some(header)
+            }
+            (Some((_, policy)), _) => Some(policy.as_str()),
+            (None, header) => header.or(request.route),
+        };
+
+        let Some(name) = requested else {
+            return Ok(self.default_policy());
+        };
+
+        if name == self.default_name {
+            return Ok(self.default_policy());
+        }
+        match self.policies.get(name) {
+            Some(policy) => Ok((name.to_string(), Arc::clone(policy))),
+            None if self.reject_unknown => Err(PolicyError::UnknownPolicy(name.to_string())),
+            None => {
+                warn!("Unknown masking policy '{}', using '{}'", name, self.default_name);
+                Ok(self.default_policy())
+            }
+        }
+    }
+
+    fn default_policy(&self) -> (String, Arc<MaskingConfig>) {
+        (self.default_name.clone(), Arc::clone(&self.default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{LimitsConfig, PoliciesConfig, ServerConfig, TargetConfig};
+
+    fn test_config(reject_unknown: bool) -> AppConfig {
+        let mut policies = PoliciesConfig {
+            reject_unknown,
+            ..Default::default()
+        };
+        policies.definitions.insert("card".to_string(), MaskingConfig::default());
+        policies.definitions.insert("hr".to_string(), MaskingConfig::default());
+        policies.api_keys.insert("k-hr".to_string(), "hr".to_string());
+        policies.identities.insert("card-team".to_string(), "card".to_string());
+        policies.header_policies.insert("card-team".to_string(), vec!["hr".to_string()]);
+
+        AppConfig {
+            server: ServerConfig {
+                port: 3000,
+                host: "0.0.0.0".to_string(),
+                max_body_bytes: 2 * 1024 * 1024,
+                tls: None,
+            },
+            target: TargetConfig {
+                url: "http://localhost:8080".to_string(),
+                timeout_ms: 5000,
+                tls: None,
+            },
+            masking: MaskingConfig::default(),
+            policies,
+            limits: LimitsConfig::default(),
+        }
+    }
+
+    #[test]
+    fn test_select_precedence() {
+        let set = PolicySet::from_config(&test_config(false));
+
+        let request = PolicyRequest {
+            header: Some("card"),
+            route: Some("hr"),
+            ..Default::default()
+        };
+        assert_eq!(set.select(&request).unwrap().0, "card");
+
+        let request = PolicyRequest {
+            api_key: Some("k-hr"),
+            route: Some("card"),
+            ..Default::default()
+        };
+        assert_eq!(set.select(&request).unwrap().0, "hr");
+
+        let request = PolicyRequest {
+            identity: Some("card-team"),
+            ..Default::default()
+        };
+        assert_eq!(set.select(&request).unwrap().0, "card");
+
+        assert_eq!(set.select(&PolicyRequest::default()).unwrap().0, DEFAULT_POLICY_NAME);
+    }
+
+    #[test]
+    fn test_header_cannot_override_mapped_policy() {
+        let set = PolicySet::from_config(&test_config(false));
+
+        let request = PolicyRequest {
+            header: Some("card"),
+            api_key: Some("k-hr"),
+            ..Default::default()
+        };
+        assert_eq!(set.select(&request).unwrap_err(), PolicyError::NotAllowed("card".to_string()));
+
+        // Naming the mapped policy itself, or one listed in header_policies, is fine
+        let request = PolicyRequest {
+            header: Some("hr"),
+            api_key: Some("k-hr"),
+            ..Default::default()
+        };
+        assert_eq!(set.select(&request).unwrap().0, "hr");
+        let request = PolicyRequest {
+            header: Some("hr"),
+            identity: Some("card-team"),
+            ..Default::default()
+        };
+        assert_eq!(set.select(&request).unwrap().0, "hr");
+    }
+
+    #[test]
+    fn test_unknown_policy_fallback_and_refusal() {
+        let request = PolicyRequest {
+            header: Some("finance"),
+            ..Default::default()
+        };
+
+        let lenient = PolicySet::from_config(&test_config(false));
+        assert_eq!(lenient.select(&request).unwrap().0, DEFAULT_POLICY_NAME);
+
+        let strict = PolicySet::from_config(&test_config(true));
+        assert_eq!(
+            strict.select(&request).unwrap_err(),
+            PolicyError::UnknownPolicy("finance".to_string())
+        );
+    }
+}
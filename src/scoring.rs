@@ -0,0 +1,230 @@
+use crate::config::ScoringConfig;
+use crate::normalize::context_before;
+
+/// Confidence of a detection with no keyword around it.
+const BASE_SCORE: f32 = 0.6;
+/// Added when a boosting keyword is near (or in the JSON key).
+const BOOST: f32 = 0.4;
+/// Taken off when a penalising keyword is near (or in the JSON key).
+const PENALTY: f32 = 0.4;
+
+/// Built-in boosting keywords per detector. Penalising words are opt-in, through `scoring.keywords`.
+const DEFAULT_BOOST: &[(&str, &[&str])] = &[
+    ("phone", &["phone", "tel", "mobile", "cell", "contact", "call", "โทร", "เบอร์", "มือถือ", "ติดต่อ"]),
+    ("credit_card", &["card", "visa", "master", "amex", "jcb", "unionpay", "pan", "บัตร"]),
+    ("thai_id", &["citizen", "national", "id card", "idcard", "id_card", "บัตรประชาชน", "ประจำตัว", "ประชาชน"]),
+    ("email", &["email", "e-mail", "mail", "อีเมล", "อีเมล์"]),
+    ("name", &["name", "customer", "ชื่อ", "ลูกค้า", "คุณ"]),
+    ("ip_address", &["ip", "client", "remote", "addr", "host"]),
+    ("mac_address", &["mac", "hwaddr", "ether", "device"]),
+    ("bank_account", &["account", "acct", "bank", "บัญชี", "ธนาคาร"]),
+    ("promptpay", &["promptpay", "prompt pay", "พร้อมเพย์"]),
+    ("passport", &["passport", "หนังสือเดินทาง"]),
+    ("driver_license", &["driver", "license", "licence", "ใบขับขี่"]),
+    ("vehicle_plate", &["plate", "registration", "ทะเบียน"]),
+    ("social_security", &["social security", "sso", "ประกันสังคม"]),
+    ("sg_nric", &["nric", "fin"]),
+    ("my_mykad", &["mykad", "ic", "kad pengenalan"]),
+    ("id_nik", &["nik", "ktp"]),
+    ("vn_cccd", &["cccd", "cmnd", "căn cước"]),
+    ("iban", &["iban", "account", "bank"]),
+    ("swift_bic", &["swift", "bic"]),
+    ("aba_routing", &["routing", "aba"]),
+    ("address", &["address", "ship", "deliver", "ที่อยู่", "จัดส่ง"]),
+    ("date_of_birth", &["dob", "birth", "born", "วันเกิด", "เกิด"]),
+];
+
+/// Detectors whose every match passes a check digit or checksum; a penalising keyword never unmasks them.
+/// Ethereum addresses are not listed: only mixed-case ones carry a checksum (see `Scorer::keep_validated`).
+pub const CHECKSUM_VALIDATED: &[&str] =
+    &["thai_id", "credit_card", "sg_nric", "iban", "aba_routing", "bitcoin_address"];
+
+/// Detector names `scoring.keywords` may configure.
+pub const DETECTORS: &[&str] = &[
+    "thai_id", "credit_card", "email", "phone", "name", "ip_address", "mac_address", "bank_account", "promptpay",
+    "passport", "driver_license", "vehicle_plate", "social_security", "sg_nric", "my_mykad", "id_nik", "vn_cccd",
+    "iban", "swift_bic", "aba_routing", "address", "date_of_birth", "bitcoin_address",
+    "ethereum_address",
+];
+
+/// `word` in `text`, not as a piece of a longer Latin word (order in reorder, ref in refund).
+fn contains_word(text: &str, word: &str) -> bool {
+    let latin = |c: char| c.is_ascii_alphanumeric();
+    text.match_indices(word).any(|(at, _)| {
+        let end = at + word.len();
+        (!word.starts_with(latin) || !text[..at].ends_with(latin))
+            && (!word.ends_with(latin) || !text[end..].starts_with(latin))
+    })
+}
+
+/// `value` written at the start of `text`, ignoring case and the spaces and dashes between characters.
+fn written_at(text: &str, value: &str) -> bool {
+    let mut rest = text.chars().peekable();
+    for (i, c) in value.chars().filter(|c| !matches!(c, ' ' | '-')).enumerate() {
+        if i > 0 {
+            while rest.next_if(|t| matches!(t, ' ' | '-')).is_some() {}
+        }
+        if !rest.next().is_some_and(|t| t.to_lowercase().eq(c.to_lowercase())) {
+            return false;
+        }
+    }
+    !rest.next().is_some_and(char::is_alphanumeric)
+}
+
+/// Whether the whole of `value` is one of `allow`, ignoring case, spaces and dashes.
+pub fn is_allowed_value(allow: &[String], value: &str) -> bool {
+    if allow.is_empty() {
+        return false;
+    }
+    let compact = |s: &str| -> String {
+        s.trim().chars().filter(|c| !matches!(c, ' ' | '-')).flat_map(char::to_lowercase).collect()
+    };
+    let value = compact(value);
+    !value.is_empty() && allow.iter().any(|allowed| compact(allowed) == value)
+}
+
+/// Scores detections by the keywords before them and the JSON key they sit under.
+pub struct Scorer<'a> {
+    config: &'a ScoringConfig,
+    key: Option<String>,
+    /// Values never masked, whatever they score
+    allow: &'a [String],
+}
+
+impl<'a> Scorer<'a> {
+    pub fn new(config: &'a ScoringConfig, allow: &'a [String], key: Option<&str>) -> Self {
+        Scorer {
+            config,
+            key: key.map(str::to_lowercase),
+            allow,
+        }
+    }
+
+    /// Confidence between 0 and 1 that the `detector` match starting at `start` is personal data.
+    pub fn score(&self, detector: &str, text: &str, start: usize) -> f32 {
+        self.score_with(detector, text, start, !CHECKSUM_VALIDATED.contains(&detector))
+    }
+
+    fn score_with(&self, detector: &str, text: &str, start: usize, penalties: bool) -> f32 {
+        let (boost, penalty): (Vec<&str>, Vec<&str>) = match self.config.keywords.get(detector) {
+            Some(keywords) => (
+                keywords.boost.iter().map(String::as_str).collect(),
+                keywords.penalty.iter().map(String::as_str).collect(),
+            ),
+            None => DEFAULT_BOOST
+                .iter()
+                .find(|(name, _)| *name == detector)
+                .map_or((Vec::new(), Vec::new()), |(_, boost)| (boost.to_vec(), Vec::new())),
+        };
+        if boost.is_empty() && penalty.is_empty() {
+            return BASE_SCORE;
+        }
+
+        let context = context_before(text, start).to_lowercase();
+        let near = |words: &[&str]| {
+            words.iter().any(|word| {
+                let word = word.to_lowercase();
+                contains_word(&context, &word) || self.key.as_deref().is_some_and(|key| contains_word(key, &word))
+            })
+        };
+        let mut score = BASE_SCORE;
+        if near(&boost) {
+            score += BOOST;
+        }
+        if penalties && near(&penalty) {
+            score -= PENALTY;
+        }
+        score.clamp(0.0, 1.0)
+    }
+
+    /// Whether the match scores at least the policy threshold and is not an allowed value.
+    pub fn keep(&self, detector: &str, text: &str, start: usize) -> bool {
+        self.score(detector, text, start) >= self.config.threshold && !self.allowed(text, start)
+    }
+
+    /// `keep` for a match whose checksum held, which no penalising keyword unmasks.
+    pub fn keep_validated(&self, detector: &str, text: &str, start: usize) -> bool {
+        self.score_with(detector, text, start, false) >= self.config.threshold && !self.allowed(text, start)
+    }
+
+    /// Whether the value starting at `start` is one of `allow_values`.
+    pub fn allowed(&self, text: &str, start: usize) -> bool {
+        self.allow.iter().any(|value| written_at(&text[start..], value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::KeywordConfig;
+
+    #[test]
+    fn test_keywords_and_json_key() {
+        let mut config = ScoringConfig::default();
+        let plain = Scorer::new(&config, &[], None);
+        assert_eq!(plain.score("phone", "x 0812345678", 2), BASE_SCORE);
+        assert_eq!(plain.score("phone", "โทร 0812345678", 10), 1.0);
+        // No penalties unless configured
+        assert!(plain.keep("phone", "order 0812345678", 6));
+
+        config.keywords.insert(
+            "phone".to_string(),
+            KeywordConfig {
+                boost: vec![],
+                penalty: vec!["order".to_string(), "ref".to_string()],
+            },
+        );
+        let penalised = Scorer::new(&config, &[], None);
+        assert!(!penalised.keep("phone", "order 0812345678", 6));
+        // reorder is not order, refund is not ref
+        assert!(penalised.keep("phone", "reorder 0812345678", 8));
+        assert!(penalised.keep("phone", "refund 0812345678", 7));
+
+        let under_key = Scorer::new(&config, &[], Some("Order_ID"));
+        assert!(!under_key.keep("phone", "0812345678", 0));
+        assert!(under_key.keep("credit_card", "4532015112830366", 0));
+        for detector in ["sg_nric", "iban", "aba_routing"] {
+            config.keywords.insert(
+                detector.to_string(),
+                KeywordConfig {
+                    boost: vec![],
+                    penalty: vec!["order".to_string()],
+                },
+            );
+        }
+        let validated = Scorer::new(&config, &[], Some("order"));
+        assert!(validated.keep("sg_nric", "S1234567D", 0));
+        assert!(validated.keep("iban", "GB82WEST12345698765432", 0));
+        assert!(validated.keep("aba_routing", "021000021", 0));
+        assert!(validated.keep_validated("phone", "0812345678", 0));
+    }
+
+    #[test]
+    fn test_configured_keywords_replace_defaults() {
+        let mut config = ScoringConfig {
+            threshold: 0.7,
+            ..Default::default()
+        };
+        config.keywords.insert(
+            "phone".to_string(),
+            KeywordConfig {
+                boost: vec!["hotline".to_string()],
+                penalty: vec![],
+            },
+        );
+        let scorer = Scorer::new(&config, &[], None);
+        assert!(scorer.keep("phone", "Hotline 0812345678", 8));
+        assert!(!scorer.keep("phone", "tel 0812345678", 4));
+    }
+
+    #[test]
+    fn test_allowed_values() {
+        let config = ScoringConfig::default();
+        let allow = ["4242424242424242".to_string(), "1-1111-11111-11-1".to_string()];
+        let scorer = Scorer::new(&config, &allow, None);
+        assert!(!scorer.keep("credit_card", "card 4242 4242 4242 4242 ok", 5));
+        assert!(!scorer.keep("thai_id", "id 1111111111111", 3));
+        // Only the exact value: a longer number is still masked
+        assert!(scorer.keep("thai_id", "id 11111111111119", 3));
+    }
+}
@@ -0,0 +1,280 @@
+use crate::config::TermsConfig;
+use crate::names::is_thai_mark;
+use aho_corasick::{AhoCorasick, MatchKind};
+use std::ops::Range;
+use std::path::Path;
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use std::{fs, io};
+use tracing::{error, info};
+
+/// Accented Latin letters (Latin-1, Latin Extended-A, Vietnamese) and the letter they fold to.
+const LATIN_ACCENTS: &[(&str, char)] = &[
+    ("àáâãäåāăąạảấầẩẫậắằẳẵặ", 'a'),
+    ("çćĉċč", 'c'),
+    ("ďđ", 'd'),
+    ("èéêëēĕėęěẹẻẽếềểễệ", 'e'),
+    ("ĝğġģ", 'g'),
+    ("ĥħ", 'h'),
+    ("ìíîïĩīĭįıỉị", 'i'),
+    ("ĵ", 'j'),
+    ("ķ", 'k'),
+    ("ĺļľŀł", 'l'),
+    ("ñńņňŉ", 'n'),
+    ("òóôõöøōŏőơọỏốồổỗộớờởỡợ", 'o'),
+    ("ŕŗř", 'r'),
+    ("śŝşš", 's'),
+    ("ţťŧ", 't'),
+    ("ùúûüũūŭůűųưụủứừửữự", 'u'),
+    ("ŵ", 'w'),
+    ("ýÿŷỳỵỷỹ", 'y'),
+    ("źżž", 'z'),
+];
+
+/// Thai tone marks and the thanthakhat, often left out or typed differently.
+fn is_thai_tone(c: char) -> bool {
+    matches!(c, '\u{0E48}'..='\u{0E4C}')
+}
+
+/// Thai leading vowels (เ แ โ ใ ไ), written before the consonant they belong to.
+fn is_thai_leading_vowel(c: char) -> bool {
+    matches!(c, '\u{0E40}'..='\u{0E44}')
+}
+
+fn is_latin_word_char(c: char) -> bool {
+    c.is_alphanumeric() && !('\u{0E00}'..='\u{0E7F}').contains(&c)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct FoldOptions {
+    case_insensitive: bool,
+    ignore_diacritics: bool,
+}
+
+/// `text` folded for matching, with the byte offset in `text` of each folded byte (plus the end).
+fn fold(text: &str, options: FoldOptions) -> (String, Vec<usize>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    let mut push = |c: char, at: usize| {
+        folded.push(c);
+        offsets.extend(std::iter::repeat_n(at, c.len_utf8()));
+    };
+    for (at, c) in text.char_indices() {
+        if options.ignore_diacritics {
+            if is_thai_tone(c) || ('\u{0300}'..='\u{036F}').contains(&c) {
+                continue;
+            }
+            let lower = c.to_lowercase().next().unwrap_or(c);
+            if let Some(&(_, base)) = LATIN_ACCENTS.iter().find(|(accented, _)| accented.contains(lower)) {
+                let base = if options.case_insensitive || lower == c { base } else { base.to_ascii_uppercase() };
+                push(base, at);
+                continue;
+            }
+        }
+        if options.case_insensitive {
+            c.to_lowercase().for_each(|lower| push(lower, at));
+        } else {
+            push(c, at);
+        }
+    }
+    offsets.push(text.len());
+    (folded, offsets)
+}
+
+/// Literal terms (project codenames, VIP and partner names) found in one pass with Aho-Corasick.
+#[derive(Debug, Clone)]
+pub struct TermDictionary {
+    automaton: AhoCorasick,
+    options: FoldOptions,
+}
+
+impl TermDictionary {
+    /// One term per line; blank lines and `#` comments are skipped.
+    fn from_list(list: &str, extra: &[String], options: FoldOptions) -> io::Result<Self> {
+        let terms: Vec<String> = list
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .chain(extra.iter().map(|term| term.trim()))
+            .map(|term| fold(term, options).0)
+            .filter(|term| !term.is_empty())
+            .collect();
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(terms)
+            .map_err(io::Error::other)?;
+        Ok(TermDictionary { automaton, options })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.automaton.patterns_len() == 0
+    }
+
+    /// Whether `text[range]` stands on its own: Latin terms don't touch a letter or digit, Thai
+    /// terms don't start after a leading vowel or end before a vowel or tone mark of the same syllable.
+    fn at_boundary(text: &str, range: &Range<usize>) -> bool {
+        let before = text[..range.start].chars().next_back();
+        let after = text[range.end..].chars().next();
+        let first = text[range.clone()].chars().next();
+        let last = text[range.clone()].chars().next_back();
+        let joined = |outside: Option<char>, inside: Option<char>| {
+            outside.is_some_and(is_latin_word_char) && inside.is_some_and(is_latin_word_char)
+        };
+        !joined(before, first)
+            && !joined(after, last)
+            && !before.is_some_and(is_thai_leading_vowel)
+            && !after.is_some_and(is_thai_mark)
+    }
+
+    /// Byte ranges of the terms in `text`.
+    pub fn find(&self, text: &str) -> Vec<Range<usize>> {
+        if self.is_empty() {
+            return Vec::new();
+        }
+        let (folded, offsets) = fold(text, self.options);
+        self.automaton
+            .find_iter(&folded)
+            .map(|mat| offsets[mat.start()]..offsets[mat.end()])
+            .filter(|range| !range.is_empty() && Self::at_boundary(text, range))
+            .collect()
+    }
+}
+
+/// A policy's term dictionary, rebuilt when its file changes on disk.
+#[derive(Debug)]
+pub struct TermList {
+    config: TermsConfig,
+    current: RwLock<Arc<TermDictionary>>,
+    last_modified: Mutex<Option<SystemTime>>,
+}
+
+fn modified(path: Option<&str>) -> Option<SystemTime> {
+    fs::metadata(path?).and_then(|m| m.modified()).ok()
+}
+
+impl TermList {
+    pub fn new(config: TermsConfig) -> io::Result<Self> {
+        let last_modified = modified(config.path.as_deref());
+        let dictionary = Self::build(&config)?;
+        Ok(TermList {
+            config,
+            current: RwLock::new(Arc::new(dictionary)),
+            last_modified: Mutex::new(last_modified),
+        })
+    }
+
+    fn build(config: &TermsConfig) -> io::Result<TermDictionary> {
+        let list = match &config.path {
+            Some(path) => fs::read_to_string(Path::new(path))?,
+            None => String::new(),
+        };
+        let options = FoldOptions {
+            case_insensitive: config.case_insensitive,
+            ignore_diacritics: config.ignore_diacritics,
+        };
+        TermDictionary::from_list(&list, &config.values, options)
+    }
+
+    pub fn current(&self) -> Arc<TermDictionary> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Rebuilds the dictionary if the file changed; a file that can't be read keeps the previous terms.
+    /// Each change is tried once, so a deleted or broken file is reported once, not on every tick.
+    pub fn reload_if_changed(&self) -> bool {
+        let mtime = modified(self.config.path.as_deref());
+        {
+            let mut last_modified = self.last_modified.lock().unwrap_or_else(|e| e.into_inner());
+            if *last_modified == mtime {
+                return false;
+            }
+            *last_modified = mtime;
+        }
+        match Self::build(&self.config) {
+            Ok(dictionary) => {
+                *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(dictionary);
+                info!("📖 Term list reloaded from {}", self.config.path.as_deref().unwrap_or_default());
+                true
+            }
+            Err(e) => {
+                error!("Failed to reload term list, keeping previous terms: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Background task polling the term file every `reload_interval_secs`.
+    pub fn spawn_watcher(self: &Arc<Self>) {
+        if self.config.path.is_none() {
+            return;
+        }
+        let list = Arc::clone(self);
+        let period = Duration::from_secs(self.config.reload_interval_secs);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                list.reload_if_changed();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn terms(list: &str, text: &str) -> Vec<String> {
+        let options = FoldOptions {
+            case_insensitive: true,
+            ignore_diacritics: true,
+        };
+        let dictionary = TermDictionary::from_list(list, &[], options).unwrap();
+        dictionary.find(text).into_iter().map(|r| text[r].to_string()).collect()
+    }
+
+    #[test]
+    fn test_case_and_diacritics() {
+        let list = "Project Nova\nNguyễn Văn An\n# partners\nบริษัท สยามพาร์ทเนอร์\n";
+        assert_eq!(terms(list, "re: PROJECT NOVA launch"), vec!["PROJECT NOVA"]);
+        assert_eq!(terms(list, "VIP nguyen van an called"), vec!["nguyen van an"]);
+        // Thanthakhat left out
+        assert_eq!(terms(list, "ส่งให้บริษัท สยามพาร์ทเนอร แล้ว"), vec!["บริษัท สยามพาร์ทเนอร"]);
+    }
+
+    #[test]
+    fn test_word_boundaries() {
+        let list = "nova\nสมศรี";
+        assert!(terms(list, "casanova supernova").is_empty());
+        assert_eq!(terms(list, "nova-2, (Nova)"), vec!["nova", "Nova"]);
+        // Thai: a whole syllable run, with or without spaces around it
+        assert_eq!(terms(list, "ติดต่อสมศรีด่วน"), vec!["สมศรี"]);
+        assert!(terms(list, "สมศรีี").is_empty());
+        assert!(terms(list, "เสมศรี").is_empty());
+    }
+
+    #[test]
+    fn test_reload_when_file_changes() {
+        let path = std::env::temp_dir().join(format!("iron-mask-terms-{}.txt", std::process::id()));
+        fs::write(&path, "alpha\n").unwrap();
+        let config = TermsConfig {
+            path: Some(path.to_string_lossy().into_owned()),
+            ..Default::default()
+        };
+        let list = TermList::new(config).unwrap();
+        assert_eq!(list.current().find("alpha beta").len(), 1);
+
+        fs::write(&path, "alpha\nbeta\n").unwrap();
+        *list.last_modified.lock().unwrap() = None;
+        assert!(list.reload_if_changed());
+        assert_eq!(list.current().find("alpha beta").len(), 2);
+
+        // Deleted: the failure is taken once and the previous terms stay
+        fs::remove_file(&path).unwrap();
+        assert!(!list.reload_if_changed());
+        assert_eq!(*list.last_modified.lock().unwrap(), None);
+        assert!(!list.reload_if_changed());
+        assert_eq!(list.current().find("alpha beta").len(), 2);
+    }
+}
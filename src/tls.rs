@@ -0,0 +1,441 @@
+use crate::config::{ConfigError, ServerTlsConfig, TargetConfig};
+use axum::{extract::ConnectInfo, Extension, Router};
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::server::graceful::GracefulShutdown;
+use hyper_util::service::TowerToHyperService;
+use tokio_rustls::rustls;
+use rustls::server::{AllowAnyAnonymousOrAuthenticatedClient, AllowAnyAuthenticatedClient, NoClientAuth};
+use rustls::{Certificate, PrivateKey, RootCertStore};
+use std::collections::HashMap;
+use std::fs::File;
+use std::future::Future;
+use std::io::BufReader;
+use std::net::{SocketAddr, ToSocketAddrs};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{Duration, SystemTime};
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tracing::{error, info, warn};
+
+/// Policy identity of a client authenticated with a certificate (mTLS).
+/// Inserted as a request extension by the TLS listener.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientIdentity(pub String);
+
+/// Holds the active rustls config and swaps it when the PEM files change on disk,
+/// so certificates can be rotated without restarting the proxy.
+pub struct TlsReloader {
+    settings: ServerTlsConfig,
+    current: RwLock<Arc<rustls::ServerConfig>>,
+    last_modified: Mutex<Vec<Option<SystemTime>>>,
+}
+
+impl TlsReloader {
+    pub fn new(settings: ServerTlsConfig) -> Result<Self, ConfigError> {
+        let server_config = build_server_config(&settings)?;
+        let last_modified = watched_mtimes(&settings);
+        Ok(Self {
+            settings,
+            current: RwLock::new(Arc::new(server_config)),
+            last_modified: Mutex::new(last_modified),
+        })
+    }
+
+    pub fn current(&self) -> Arc<rustls::ServerConfig> {
+        self.current.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    /// Rebuilds the rustls config if any watched file changed.
+    /// A broken cert/key keeps the previous config active instead of taking the listener down.
+    pub fn reload_if_changed(&self) -> bool {
+        let mtimes = watched_mtimes(&self.settings);
+        {
+            let last = self.last_modified.lock().unwrap_or_else(|e| e.into_inner());
+            if *last == mtimes {
+                return false;
+            }
+        }
+
+        match build_server_config(&self.settings) {
+            Ok(server_config) => {
+                *self.current.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(server_config);
+                *self.last_modified.lock().unwrap_or_else(|e| e.into_inner()) = mtimes;
+                info!("🔐 TLS certificates reloaded from {}", self.settings.cert_path);
+                true
+            }
+            Err(e) => {
+                error!("Failed to reload TLS certificates, keeping previous ones: {}", e);
+                false
+            }
+        }
+    }
+
+    /// Background task polling the PEM files every `reload_interval_secs`.
+    pub fn spawn_watcher(self: &Arc<Self>) {
+        let reloader = Arc::clone(self);
+        let period = Duration::from_secs(self.settings.reload_interval_secs);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                reloader.reload_if_changed();
+            }
+        });
+    }
+
+    fn identity_map(&self) -> Option<&HashMap<String, String>> {
+        self.settings.client_auth.as_ref().map(|c| &c.identities)
+    }
+}
+
+fn watched_mtimes(settings: &ServerTlsConfig) -> Vec<Option<SystemTime>> {
+    let mut paths = vec![settings.cert_path.as_str(), settings.key_path.as_str()];
+    if let Some(client_auth) = &settings.client_auth {
+        paths.push(client_auth.ca_path.as_str());
+    }
+    paths
+        .into_iter()
+        .map(|p| std::fs::metadata(p).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+pub fn build_server_config(settings: &ServerTlsConfig) -> Result<rustls::ServerConfig, ConfigError> {
+    let certs = load_certs(&settings.cert_path)?;
+    let key = load_private_key(&settings.key_path)?;
+
+    let verifier = match &settings.client_auth {
+        Some(client_auth) => {
+            let roots = load_roots(&client_auth.ca_path)?;
+            if client_auth.required {
+                AllowAnyAuthenticatedClient::new(roots).boxed()
+            } else {
+                AllowAnyAnonymousOrAuthenticatedClient::new(roots).boxed()
+            }
+        }
+        None => NoClientAuth::boxed(),
+    };
+
+    let mut server_config = rustls::ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(verifier)
+        .with_single_cert(certs, key)
+        .map_err(|e| ConfigError::InvalidConfig(format!("TLS certificate/key mismatch: {}", e)))?;
+    server_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(server_config)
+}
+
+pub(crate) fn load_certs(path: &str) -> Result<Vec<Certificate>, ConfigError> {
+    let file = File::open(path).map_err(|e| ConfigError::FileNotFound(format!("{}: {}", path, e)))?;
+    let certs = rustls_pemfile::certs(&mut BufReader::new(file))
+        .map_err(|e| ConfigError::ParseError(format!("{}: {}", path, e)))?;
+    if certs.is_empty() {
+        return Err(ConfigError::InvalidConfig(format!("No certificates found in {}", path)));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+pub(crate) fn load_private_key(path: &str) -> Result<PrivateKey, ConfigError> {
+    let file = File::open(path).map_err(|e| ConfigError::FileNotFound(format!("{}: {}", path, e)))?;
+    let items = rustls_pemfile::read_all(&mut BufReader::new(file))
+        .map_err(|e| ConfigError::ParseError(format!("{}: {}", path, e)))?;
+
+    items
+        .into_iter()
+        .find_map(|item| match item {
+            rustls_pemfile::Item::PKCS8Key(key)
+            | rustls_pemfile::Item::RSAKey(key)
+            | rustls_pemfile::Item::ECKey(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or_else(|| ConfigError::InvalidConfig(format!("No private key found in {}", path)))
+}
+
+pub(crate) fn load_roots(path: &str) -> Result<RootCertStore, ConfigError> {
+    let mut roots = RootCertStore::empty();
+    for cert in load_certs(path)? {
+        roots
+            .add(&cert)
+            .map_err(|e| ConfigError::InvalidConfig(format!("Bad CA certificate in {}: {}", path, e)))?;
+    }
+    Ok(roots)
+}
+
+/// Maps a client certificate to a policy identity by its full subject DN. A CN alone never
+/// selects an identity: any certificate from the CA could carry a mapped identity's name.
+pub fn identity_from_cert(der: &[u8], identities: &HashMap<String, String>) -> Option<String> {
+    let (_, cert) = x509_parser::parse_x509_certificate(der).ok()?;
+    let subject = normalize_dn(&cert.subject().to_string());
+    identities
+        .iter()
+        .find(|(pattern, _)| normalize_dn(pattern) == subject)
+        .map(|(_, identity)| identity.clone())
+}
+
+/// The attributes of a DN as a sorted set, so RDN order and spacing don't matter:
+/// "O=Acme, CN=card-svc" and "CN=card-svc,O=Acme" compare equal.
+fn normalize_dn(dn: &str) -> Vec<String> {
+    let mut attributes: Vec<String> = dn
+        .split([',', '+'])
+        .map(str::trim)
+        .filter(|rdn| !rdn.is_empty())
+        .map(|rdn| match rdn.split_once('=') {
+            Some((kind, value)) => format!("{}={}", kind.trim().to_ascii_uppercase(), value.trim()),
+            None => rdn.to_string(),
+        })
+        .collect();
+    attributes.sort();
+    attributes.dedup();
+    attributes
+}
+
+/// Clients that haven't finished the TLS handshake by then are dropped.
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Accept loop for the TLS listener. Each connection gets the client address and,
+/// for mTLS, its `ClientIdentity` as request extensions.
+pub async fn serve(
+    listener: TcpListener,
+    app: Router,
+    reloader: Arc<TlsReloader>,
+    shutdown: impl Future<Output = ()>,
+) {
+    let graceful = GracefulShutdown::new();
+    tokio::pin!(shutdown);
+
+    loop {
+        let (tcp, addr) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(conn) => conn,
+                Err(e) => {
+                    warn!("Failed to accept connection: {}", e);
+                    continue;
+                }
+            },
+            _ = &mut shutdown => break,
+        };
+
+        let acceptor = TlsAcceptor::from(reloader.current());
+        let reloader = Arc::clone(&reloader);
+        let app = app.clone();
+        let watcher = graceful.watcher();
+
+        tokio::spawn(async move {
+            let stream = match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(tcp)).await {
+                Ok(Ok(s)) => s,
+                Ok(Err(e)) => {
+                    warn!("TLS handshake with {} failed: {}", addr, e);
+                    return;
+                }
+                Err(_) => {
+                    warn!("TLS handshake with {} timed out", addr);
+                    return;
+                }
+            };
+
+            let identity = match (stream.get_ref().1.peer_certificates(), reloader.identity_map()) {
+                (Some([leaf, ..]), Some(identities)) => identity_from_cert(&leaf.0, identities),
+                _ => None,
+            };
+
+            let mut app = app.layer(Extension(ConnectInfo::<SocketAddr>(addr)));
+            if let Some(identity) = identity {
+                app = app.layer(Extension(ClientIdentity(identity)));
+            }
+
+            let builder = auto::Builder::new(TokioExecutor::new());
+            let conn = builder.serve_connection_with_upgrades(
+                TokioIo::new(stream),
+                TowerToHyperService::new(app),
+            );
+            if let Err(e) = watcher.watch(conn.into_owned()).await {
+                warn!("Connection from {} ended with error: {}", addr, e);
+            }
+        });
+    }
+
+    drop(listener);
+    if tokio::time::timeout(Duration::from_secs(30), graceful.shutdown()).await.is_err() {
+        warn!("Timed out waiting for TLS connections to close");
+    }
+}
+
+/// Builds the upstream HTTP client from `target` and returns it with the URL to post to.
+/// With an SNI override the URL host is replaced by the SNI name, which is pinned
+/// to the addresses of the original host so the connection still goes there.
+pub fn build_upstream_client(target: &TargetConfig) -> Result<(reqwest::Client, String), ConfigError> {
+    let mut builder = reqwest::Client::builder().timeout(Duration::from_millis(target.timeout_ms));
+    let mut url = target.url.clone();
+
+    if let Some(tls) = &target.tls {
+        builder = builder.tls_built_in_root_certs(tls.use_builtin_roots);
+
+        if let Some(ca_path) = &tls.ca_path {
+            let pem = std::fs::read(ca_path)
+                .map_err(|e| ConfigError::FileNotFound(format!("{}: {}", ca_path, e)))?;
+            let certs = reqwest::Certificate::from_pem_bundle(&pem)
+                .map_err(|e| ConfigError::ParseError(format!("{}: {}", ca_path, e)))?;
+            for cert in certs {
+                builder = builder.add_root_certificate(cert);
+            }
+        }
+
+        if let (Some(cert_path), Some(key_path)) = (&tls.client_cert_path, &tls.client_key_path) {
+            // reqwest (rustls) wants the key and the chain in a single PEM buffer
+            let mut pem = std::fs::read(key_path)
+                .map_err(|e| ConfigError::FileNotFound(format!("{}: {}", key_path, e)))?;
+            pem.push(b'\n');
+            pem.extend(
+                std::fs::read(cert_path)
+                    .map_err(|e| ConfigError::FileNotFound(format!("{}: {}", cert_path, e)))?,
+            );
+            let identity = reqwest::Identity::from_pem(&pem)
+                .map_err(|e| ConfigError::ParseError(format!("{}: {}", cert_path, e)))?;
+            builder = builder.identity(identity);
+        }
+
+        if let Some(version) = &tls.min_version {
+            let version = match version.as_str() {
+                "1.3" => reqwest::tls::Version::TLS_1_3,
+                _ => reqwest::tls::Version::TLS_1_2,
+            };
+            builder = builder.min_tls_version(version);
+        }
+
+        if let Some(sni) = &tls.sni {
+            let mut parsed = reqwest::Url::parse(&target.url)
+                .map_err(|e| ConfigError::InvalidConfig(format!("Bad target URL: {}", e)))?;
+            let host = parsed
+                .host_str()
+                .ok_or_else(|| ConfigError::InvalidConfig("Target URL has no host".to_string()))?
+                .to_string();
+            let port = parsed.port_or_known_default().unwrap_or(443);
+            let addrs: Vec<SocketAddr> = (host.as_str(), port)
+                .to_socket_addrs()
+                .map_err(|e| ConfigError::InvalidConfig(format!("Cannot resolve {}: {}", host, e)))?
+                .collect();
+            parsed
+                .set_host(Some(sni))
+                .map_err(|e| ConfigError::InvalidConfig(format!("Bad SNI name {}: {}", sni, e)))?;
+            builder = builder.resolve_to_addrs(sni, &addrs);
+            url = parsed.to_string();
+        }
+
+        if tls.insecure_skip_verify {
+            warn!("⚠️  Upstream TLS verification is DISABLED (target.tls.insecure_skip_verify). Local testing only!");
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+    }
+
+    let client = builder
+        .build()
+        .map_err(|e| ConfigError::InvalidConfig(format!("Failed to create HTTP client: {}", e)))?;
+    Ok((client, url))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_dn_ignores_spaces() {
+        assert_eq!(normalize_dn("CN=card-svc, O=Acme"), normalize_dn("CN=card-svc,O=Acme"));
+    }
+
+    #[test]
+    fn test_normalize_dn_ignores_rdn_order() {
+        assert_eq!(normalize_dn("O=Acme, OU=Cards, CN=card-svc"), normalize_dn("cn=card-svc,O=Acme,OU=Cards"));
+        assert_ne!(normalize_dn("CN=card-svc,O=Acme"), normalize_dn("CN=card-svc,O=Other"));
+    }
+
+    /// Self-signed certificate with the given subject attributes, as (cert PEM, key PEM, cert DER).
+    fn generate_cert(subject: &[(rcgen::DnType, &str)]) -> (String, String, Vec<u8>) {
+        let mut params = rcgen::CertificateParams::new(vec!["localhost".to_string()]);
+        params.distinguished_name = rcgen::DistinguishedName::new();
+        for (kind, value) in subject {
+            params.distinguished_name.push(kind.clone(), *value);
+        }
+        let cert = rcgen::Certificate::from_params(params).unwrap();
+        (cert.serialize_pem().unwrap(), cert.serialize_private_key_pem(), cert.serialize_der().unwrap())
+    }
+
+    #[test]
+    fn test_identity_from_cert_needs_full_dn() {
+        let (_, _, card_svc) =
+            generate_cert(&[(rcgen::DnType::OrganizationName, "Acme"), (rcgen::DnType::CommonName, "card-svc")]);
+        let identities = HashMap::from([("CN=card-svc,O=Acme".to_string(), "card-team".to_string())]);
+        assert_eq!(identity_from_cert(&card_svc, &identities), Some("card-team".to_string()));
+
+        // Same CN from another organisation, or a CN named after an identity: no identity
+        let (_, _, other) =
+            generate_cert(&[(rcgen::DnType::OrganizationName, "Other"), (rcgen::DnType::CommonName, "card-svc")]);
+        assert_eq!(identity_from_cert(&other, &identities), None);
+        let (_, _, impostor) = generate_cert(&[(rcgen::DnType::CommonName, "card-team")]);
+        assert_eq!(identity_from_cert(&impostor, &identities), None);
+        let cn_only = HashMap::from([("card-svc".to_string(), "card-team".to_string())]);
+        assert_eq!(identity_from_cert(&card_svc, &cn_only), None);
+    }
+
+    #[test]
+    fn test_reload_when_cert_changes() {
+        let dir = std::env::temp_dir().join(format!("iron-mask-tls-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (cert_path, key_path) = (dir.join("server.crt"), dir.join("server.key"));
+        let write_cert = |cn: &str| {
+            let (cert, key, _) = generate_cert(&[(rcgen::DnType::CommonName, cn)]);
+            std::fs::write(&cert_path, cert).unwrap();
+            std::fs::write(&key_path, key).unwrap();
+        };
+        write_cert("first");
+        let reloader = TlsReloader::new(ServerTlsConfig {
+            cert_path: cert_path.to_string_lossy().into_owned(),
+            key_path: key_path.to_string_lossy().into_owned(),
+            reload_interval_secs: 30,
+            client_auth: None,
+        })
+        .unwrap();
+        let first = reloader.current();
+        assert!(!reloader.reload_if_changed());
+
+        write_cert("second");
+        reloader.last_modified.lock().unwrap().clear();
+        assert!(reloader.reload_if_changed());
+        assert!(!Arc::ptr_eq(&first, &reloader.current()));
+
+        // A broken certificate keeps the one in use
+        let second = reloader.current();
+        std::fs::write(&cert_path, "not a certificate").unwrap();
+        reloader.last_modified.lock().unwrap().clear();
+        assert!(!reloader.reload_if_changed());
+        assert!(Arc::ptr_eq(&second, &reloader.current()));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_cert_file_is_reported() {
+        let settings = ServerTlsConfig {
+            cert_path: "does/not/exist.crt".to_string(),
+            key_path: "does/not/exist.key".to_string(),
+            reload_interval_secs: 30,
+            client_auth: None,
+        };
+        assert!(matches!(build_server_config(&settings), Err(ConfigError::FileNotFound(_))));
+    }
+
+    #[test]
+    fn test_upstream_sni_override_rewrites_host() {
+        let target = TargetConfig {
+            url: "https://127.0.0.1:8443/ingest".to_string(),
+            timeout_ms: 5000,
+            tls: Some(crate::config::UpstreamTlsConfig {
+                sni: Some("ingest.internal".to_string()),
+                use_builtin_roots: true,
+                ..Default::default()
+            }),
+        };
+        let (_, url) = build_upstream_client(&target).unwrap();
+        assert_eq!(url, "https://ingest.internal:8443/ingest");
+    }
+}
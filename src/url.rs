@@ -0,0 +1,172 @@
+use crate::encoded::{percent_decode, percent_encode};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::ops::Range;
+
+lazy_static! {
+    // Absolute URL: https://shop.example/users/1103700012346/orders?email=a%40b.com
+    static ref RE_URL: Regex = Regex::new(r#"(?i)\b[a-z][a-z0-9+.-]*://[^\s"'<>]+"#).unwrap();
+
+    // Request target after the method in an access log, or a whole value that is a path:
+    // GET /users/1103700012346/orders?tel=0812345678
+    static ref RE_PATH: Regex =
+        Regex::new(r#"(?:^|\b(?:GET|POST|PUT|PATCH|DELETE|HEAD|OPTIONS) )(/[^\s"'<>]*)"#).unwrap();
+}
+
+/// Sentence punctuation that ends a URL rather than belonging to it.
+const TRAILING: &[char] = &['.', ',', ';', ':', '!', '?', ')', ']', '}'];
+
+/// Absolute URLs and request paths in `text`.
+pub fn find_urls(text: &str) -> Vec<Range<usize>> {
+    if !text.contains('/') {
+        return Vec::new();
+    }
+    let mut found: Vec<Range<usize>> = RE_URL.find_iter(text).map(|mat| mat.range()).collect();
+    for caps in RE_PATH.captures_iter(text) {
+        let path = caps.get(1).unwrap().range();
+        if !found.iter().any(|f| f.start < path.end && path.start < f.end) {
+            found.push(path);
+        }
+    }
+    for range in &mut found {
+        range.end = range.start + text[range.clone()].trim_end_matches(TRAILING).len();
+    }
+    found.retain(|range| range.len() > 1);
+    found.sort_by_key(|range| range.start);
+    found
+}
+
+/// Decodes one URL component, hands it to `mask` and re-encodes it if it changed.
+/// In a query, `+` stands for a space.
+fn mask_component(raw: &str, plus_is_space: bool, mask: impl FnOnce(&str) -> String) -> String {
+    let spaced = if plus_is_space { raw.replace('+', " ") } else { raw.to_string() };
+    let Some(decoded) = percent_decode(&spaced) else {
+        return raw.to_string();
+    };
+    let masked = mask(&decoded);
+    if masked == decoded {
+        return raw.to_string();
+    }
+    let encoded = percent_encode(&masked, raw);
+    if plus_is_space && raw.contains('+') { encoded.replace("%20", "+") } else { encoded }
+}
+
+/// Masks the authority (`user:pass@host:port`): the user info and the host go to `text` on their
+/// own, so an IP address or an email used as a user name is masked; the port is kept.
+fn mask_authority(authority: &str, text: &impl Fn(&str, Option<&str>) -> String) -> String {
+    let (userinfo, host_port) = authority.rsplit_once('@').map_or((None, authority), |(u, h)| (Some(u), h));
+    let host_end = match host_port.strip_prefix('[') {
+        // [2001:db8::1]:8443
+        Some(v6) => v6.find(']').map_or(host_port.len(), |end| end + 2),
+        None => host_port
+            .rsplit_once(':')
+            .filter(|(_, port)| port.bytes().all(|b| b.is_ascii_digit()))
+            .map_or(host_port.len(), |(host, _)| host.len()),
+    };
+    let (host, port) = host_port.split_at(host_end);
+
+    let mut masked = String::with_capacity(authority.len());
+    if let Some(userinfo) = userinfo {
+        masked.push_str(&mask_component(userinfo, false, |decoded| text(decoded, None)));
+        masked.push('@');
+    }
+    masked.push_str(&text(host, None));
+    masked.push_str(port);
+    masked
+}
+
+/// Masks a URL part by part: the user info and host, each path segment (with the segment before
+/// it as context) and the fragment go to `text`, each query parameter to `param` with its name.
+/// The scheme and port are kept, and whatever changed is percent-encoded back the way the URL had it.
+pub fn mask_url(
+    url: &str,
+    text: impl Fn(&str, Option<&str>) -> String,
+    param: impl Fn(&str, &str) -> String,
+) -> String {
+    let authority_start = url.find("://").map_or(0, |at| at + 3);
+    let authority_end = if authority_start == 0 {
+        0
+    } else {
+        url[authority_start..].find(['/', '?', '#']).map_or(url.len(), |end| authority_start + end)
+    };
+    let rest = &url[authority_end..];
+    let (rest, fragment) = rest.split_once('#').map_or((rest, None), |(rest, f)| (rest, Some(f)));
+    let (path, query) = rest.split_once('?').map_or((rest, None), |(path, q)| (path, Some(q)));
+
+    let mut masked = url[..authority_start].to_string();
+    masked.push_str(&mask_authority(&url[authority_start..authority_end], &text));
+    let mut previous: Option<String> = None;
+    for (i, segment) in path.split('/').enumerate() {
+        if i > 0 {
+            masked.push('/');
+        }
+        masked.push_str(&mask_component(segment, false, |decoded| text(decoded, previous.as_deref())));
+        previous = percent_decode(segment);
+    }
+
+    if let Some(query) = query {
+        masked.push('?');
+        for (i, pair) in query.split('&').enumerate() {
+            if i > 0 {
+                masked.push('&');
+            }
+            match pair.split_once('=') {
+                Some((name, value)) => {
+                    let key = percent_decode(&name.replace('+', " ")).unwrap_or_else(|| name.to_string());
+                    masked.push_str(name);
+                    masked.push('=');
+                    masked.push_str(&mask_component(value, true, |decoded| param(&key, decoded)));
+                }
+                None => masked.push_str(&mask_component(pair, true, |decoded| text(decoded, None))),
+            }
+        }
+    }
+
+    if let Some(fragment) = fragment {
+        masked.push('#');
+        masked.push_str(&mask_component(fragment, false, |decoded| text(decoded, None)));
+    }
+    masked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn urls(text: &str) -> Vec<&str> {
+        find_urls(text).into_iter().map(|range| &text[range]).collect()
+    }
+
+    #[test]
+    fn test_find_urls() {
+        assert_eq!(
+            urls("see https://shop.example/a?b=1, then GET /users/42/orders HTTP/1.1"),
+            vec!["https://shop.example/a?b=1", "/users/42/orders"]
+        );
+        assert_eq!(urls("/api/v1/customers?id=7"), vec!["/api/v1/customers?id=7"]);
+        // Dates and fractions are not paths
+        assert!(urls("วันที่ 12/05/2530 ครึ่ง 1/2").is_empty());
+    }
+
+    #[test]
+    fn test_mask_url_by_part() {
+        let digits = |text: &str, previous: Option<&str>| match previous {
+            Some("users") => text.replace(|c: char| c.is_ascii_digit(), "X"),
+            _ => text.to_string(),
+        };
+        let param = |name: &str, value: &str| match name {
+            "email" => "jo***@test.com".to_string(),
+            "q" => value.to_uppercase(),
+            _ => value.to_string(),
+        };
+        assert_eq!(
+            mask_url("https://a.example:8443/users/1103/orders/99?email=john%40test.com&q=hi+there&page=2#top", digits, param),
+            "https://a.example:8443/users/XXXX/orders/99?email=jo%2A%2A%2A%40test.com&q=HI+THERE&page=2#top"
+        );
+
+        // User info and host are masked on their own, the port is kept
+        let upper = |text: &str, _: Option<&str>| text.to_uppercase();
+        assert_eq!(mask_url("ftp://jo%40x.co:pw@files.example:21/a", upper, param), "ftp://JO%40X.CO:PW@FILES.EXAMPLE:21/A");
+        assert_eq!(mask_url("http://[fe80::1]:8080", upper, param), "http://[FE80::1]:8080");
+    }
+}
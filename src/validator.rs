@@ -21,6 +21,46 @@ pub fn is_luhn_valid(s: &str) -> bool {
     sum % 10 == 0
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum CardNetwork {
+    Visa,
+    Mastercard,
+    Amex,
+    Jcb,
+    UnionPay,
+}
+
+// ระบุเครือข่ายบัตรจาก IIN/BIN (เลขนำหน้า) และความยาวที่เครือข่ายนั้นออกจริง
+pub fn card_network(digits: &str) -> Option<CardNetwork> {
+    if !digits.chars().all(|c| c.is_ascii_digit()) || digits.len() < 13 {
+        return None;
+    }
+    let prefix = |n: usize| digits[..n].parse::<u32>().unwrap_or(0);
+    let len = digits.len();
+
+    let network = match (prefix(1), prefix(2), prefix(4)) {
+        (4, _, _) => CardNetwork::Visa,
+        (_, 51..=55, _) | (_, _, 2221..=2720) => CardNetwork::Mastercard,
+        (_, 34 | 37, _) => CardNetwork::Amex,
+        (_, _, 3528..=3589) => CardNetwork::Jcb,
+        (_, 62, _) => CardNetwork::UnionPay,
+        _ => return None,
+    };
+    let length_ok = match network {
+        CardNetwork::Visa => matches!(len, 13 | 16 | 19),
+        CardNetwork::Mastercard => len == 16,
+        CardNetwork::Amex => len == 15,
+        CardNetwork::Jcb | CardNetwork::UnionPay => (16..=19).contains(&len),
+    };
+    length_ok.then_some(network)
+}
+
+// บัตรจริง = IIN/ความยาวตรงเครือข่าย + Luhn ผ่าน (ตัดเลข order ที่บังเอิญผ่าน Luhn ออก)
+pub fn is_card_number(s: &str) -> bool {
+    let digits: String = s.chars().filter(|c| c.is_ascii_digit()).collect();
+    card_network(&digits).is_some() && is_luhn_valid(&digits)
+}
+
 // src/validator.rs
 pub fn is_thai_id(id: &str) -> bool {
     // 1. ตรวจสอบเบื้องต้น
@@ -41,6 +81,349 @@ pub fn is_thai_id(id: &str) -> bool {
     id.chars().last().and_then(|c| c.to_digit(10)) == Some(check_digit)
 }
 
+// สิงคโปร์ NRIC/FIN: ตัวอักษรนำหน้า + 7 หลัก + ตัวอักษรตรวจสอบ เช่น S1234567D
+pub fn is_sg_nric(id: &str) -> bool {
+    let bytes = id.as_bytes();
+    if bytes.len() != 9 || !bytes[1..8].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+
+    const WEIGHTS: [u32; 7] = [2, 7, 6, 5, 4, 3, 2];
+    let mut sum: u32 = bytes[1..8]
+        .iter()
+        .zip(WEIGHTS)
+        .map(|(d, w)| u32::from(d - b'0') * w)
+        .sum();
+    // T/G (เกิดหรือออกบัตรตั้งแต่ปี 2000) และ M (FIN ตั้งแต่ปี 2022) บวกค่าเพิ่มก่อนหาร
+    sum += match bytes[0] {
+        b'T' | b'G' => 4,
+        b'M' => 3,
+        _ => 0,
+    };
+    let r = (sum % 11) as usize;
+
+    let expected = match bytes[0] {
+        b'S' | b'T' => b"JZIHGFEDCBA"[r],
+        b'F' | b'G' => b"XWUTRQPNMLK"[r],
+        b'M' => b"KLJNPQRTUWX"[10 - r],
+        _ => return false,
+    };
+    bytes[8] == expected
+}
+
+pub(crate) fn is_valid_date(year: u32, month: u32, day: u32) -> bool {
+    let days = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if year.is_multiple_of(4) => 29,
+        2 => 28,
+        _ => return false,
+    };
+    (1..=days).contains(&day)
+}
+
+fn two_digits(digits: &[u8], at: usize) -> u32 {
+    u32::from(digits[at] - b'0') * 10 + u32::from(digits[at + 1] - b'0')
+}
+
+fn ascii_digits(id: &str) -> Vec<u8> {
+    id.bytes().filter(u8::is_ascii_digit).collect()
+}
+
+// มาเลเซีย MyKad: YYMMDD-PB-###G วันเกิดต้องมีจริง และรหัสรัฐ (PB) ต้องอยู่ในรายการของ JPN
+pub fn is_my_mykad(id: &str) -> bool {
+    let digits = ascii_digits(id);
+    if digits.len() != 12 || id.bytes().any(|b| !b.is_ascii_digit() && b != b'-') {
+        return false;
+    }
+    let (year, month, day) = (two_digits(&digits, 0), two_digits(&digits, 2), two_digits(&digits, 4));
+    let place = two_digits(&digits, 6);
+    let known_place = matches!(place, 1..=16 | 21..=68 | 71 | 72 | 74..=79 | 82..=93 | 98 | 99);
+    is_valid_date(year, month, day) && known_place
+}
+
+// อินโดนีเซีย NIK 16 หลัก: จังหวัด(2) อำเภอ(2) ตำบล(2) DDMMYY (ผู้หญิง DD+40) ลำดับ(4)
+pub fn is_id_nik(id: &str) -> bool {
+    let digits = ascii_digits(id);
+    if digits.len() != 16 || id.len() != 16 {
+        return false;
+    }
+    let province = two_digits(&digits, 0);
+    let known_province = matches!(province, 11..=19 | 21 | 31..=36 | 51..=53 | 61..=65 | 71..=76 | 81 | 82 | 91..=96);
+    let regency = two_digits(&digits, 2);
+    let day = two_digits(&digits, 6);
+    let day = if day > 40 { day - 40 } else { day };
+    let (month, year) = (two_digits(&digits, 8), two_digits(&digits, 10));
+    let serial = &digits[12..];
+    known_province && regency != 0 && is_valid_date(year, month, day) && serial != b"0000"
+}
+
+// รหัสจังหวัดที่ใช้ในเลข CCCD ของเวียดนาม
+const VN_PROVINCES: &[u32] = &[
+    1, 2, 4, 6, 8, 10, 11, 12, 14, 15, 17, 19, 20, 22, 24, 25, 26, 27, 30, 31, 33, 34, 35, 36, 37, 38, 40, 42,
+    44, 45, 46, 48, 49, 51, 52, 54, 56, 58, 60, 62, 64, 66, 67, 68, 70, 72, 74, 75, 77, 79, 80, 82, 83, 84,
+    86, 87, 89, 91, 92, 93, 94, 95, 96,
+];
+
+// เวียดนาม CCCD 12 หลัก: รหัสจังหวัด(3) เพศ/ศตวรรษ(1: 0-3 สำหรับปี 1900-2099) ปีเกิด(2) สุ่ม(6)
+pub fn is_vn_cccd(id: &str) -> bool {
+    let digits = ascii_digits(id);
+    if digits.len() != 12 || id.len() != 12 {
+        return false;
+    }
+    let province = u32::from(digits[0] - b'0') * 100 + two_digits(&digits, 1);
+    VN_PROVINCES.contains(&province) && digits[3] <= b'3'
+}
+
+// ความยาว IBAN ของแต่ละประเทศ (ตาม SWIFT IBAN registry)
+const IBAN_LENGTHS: &[(&str, usize)] = &[
+    ("AD", 24), ("AE", 23), ("AL", 28), ("AT", 20), ("AZ", 28), ("BA", 20), ("BE", 16), ("BG", 22),
+    ("BH", 22), ("BI", 27), ("BR", 29), ("BY", 28), ("CH", 21), ("CR", 22), ("CY", 28), ("CZ", 24),
+    ("DE", 22), ("DJ", 27), ("DK", 18), ("DO", 28), ("EE", 20), ("EG", 29), ("ES", 24), ("FI", 18),
+    ("FK", 18), ("FO", 18), ("FR", 27), ("GB", 22), ("GE", 22), ("GI", 23), ("GL", 18), ("GR", 27),
+    ("GT", 28), ("HR", 21), ("HU", 28), ("IE", 22), ("IL", 23), ("IQ", 23), ("IS", 26), ("IT", 27),
+    ("JO", 30), ("KW", 30), ("KZ", 20), ("LB", 28), ("LC", 32), ("LI", 21), ("LT", 20), ("LU", 20),
+    ("LV", 21), ("LY", 25), ("MC", 27), ("MD", 24), ("ME", 22), ("MK", 19), ("MN", 20), ("MR", 27),
+    ("MT", 31), ("MU", 30), ("NI", 28), ("NL", 18), ("NO", 15), ("OM", 23), ("PK", 24), ("PL", 28),
+    ("PS", 29), ("PT", 25), ("QA", 29), ("RO", 24), ("RS", 22), ("RU", 33), ("SA", 24), ("SC", 31),
+    ("SD", 18), ("SE", 24), ("SI", 19), ("SK", 24), ("SM", 27), ("SO", 23), ("ST", 25), ("SV", 28),
+    ("TL", 23), ("TN", 24), ("TR", 26), ("UA", 29), ("VA", 22), ("VG", 24), ("XK", 20), ("YE", 30),
+];
+
+// IBAN: ความยาวต้องตรงกับประเทศ และ mod-97 ต้องได้ 1 (ISO 13616) ช่องว่างระหว่างกลุ่มถูกข้าม
+pub fn is_iban(iban: &str) -> bool {
+    let compact: Vec<u8> = iban.bytes().filter(|b| *b != b' ').collect();
+    if compact.len() < 15 || !compact.iter().all(u8::is_ascii_alphanumeric) {
+        return false;
+    }
+    let country = std::str::from_utf8(&compact[..2]).unwrap_or("");
+    let expected = IBAN_LENGTHS.iter().find(|(code, _)| *code == country).map(|(_, len)| *len);
+    if expected != Some(compact.len()) || !compact[2..4].iter().all(u8::is_ascii_digit) {
+        return false;
+    }
+
+    // ย้าย 4 ตัวแรกไปท้าย แปลง A=10..Z=35 แล้วคำนวณ mod 97 ทีละหลัก
+    let mut remainder: u32 = 0;
+    for &b in compact[4..].iter().chain(&compact[..4]) {
+        let value = match b {
+            b'0'..=b'9' => u32::from(b - b'0'),
+            b'A'..=b'Z' => u32::from(b - b'A') + 10,
+            b'a'..=b'z' => u32::from(b - b'a') + 10,
+            _ => return false,
+        };
+        remainder = if value >= 10 { (remainder * 100 + value) % 97 } else { (remainder * 10 + value) % 97 };
+    }
+    remainder == 1
+}
+
+// รหัสประเทศ ISO 3166-1 alpha-2 และ XK (โคโซโว) ที่ SWIFT ใช้ด้วย
+const COUNTRY_CODES: &str = "AD AE AF AG AI AL AM AO AQ AR AS AT AU AW AX AZ BA BB BD BE BF BG BH BI BJ BL BM BN BO BQ \
+BR BS BT BV BW BY BZ CA CC CD CF CG CH CI CK CL CM CN CO CR CU CV CW CX CY CZ DE DJ DK DM DO DZ EC EE EG EH ER ES ET \
+FI FJ FK FM FO FR GA GB GD GE GF GG GH GI GL GM GN GP GQ GR GS GT GU GW GY HK HM HN HR HT HU ID IE IL IM IN IO IQ IR \
+IS IT JE JM JO JP KE KG KH KI KM KN KP KR KW KY KZ LA LB LC LI LK LR LS LT LU LV LY MA MC MD ME MF MG MH MK ML MM MN \
+MO MP MQ MR MS MT MU MV MW MX MY MZ NA NC NE NF NG NI NL NO NP NR NU NZ OM PA PE PF PG PH PK PL PM PN PR PS PT PW PY \
+QA RE RO RS RU RW SA SB SC SD SE SG SH SI SJ SK SL SM SN SO SR SS ST SV SX SY SZ TC TD TF TG TH TJ TK TL TM TN TO TR \
+TT TV TW TZ UA UG UM US UY UZ VA VC VE VG VI VN VU WF WS XK YE YT ZA ZM ZW";
+
+pub fn is_country_code(code: &str) -> bool {
+    code.len() == 2 && COUNTRY_CODES.split_whitespace().any(|c| c == code)
+}
+
+// SWIFT/BIC: ธนาคาร(4 ตัวอักษร) ประเทศ(2 ตัวอักษร ISO) ที่ตั้ง(2) สาขา(3, ไม่บังคับ) เช่น BKKBTHBK, DEUTDEFF500
+pub fn is_swift_bic(bic: &str) -> bool {
+    let bytes = bic.as_bytes();
+    (bytes.len() == 8 || bytes.len() == 11)
+        && bytes[..6].iter().all(u8::is_ascii_uppercase)
+        && bytes[6..].iter().all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        && is_country_code(&bic[4..6])
+}
+
+// ABA routing number 9 หลัก: 2 หลักแรกเป็นเขต Federal Reserve และน้ำหนัก 3-7-1 รวมกันหาร 10 ลงตัว
+pub fn is_aba_routing(routing: &str) -> bool {
+    let digits = ascii_digits(routing);
+    if digits.len() != 9 || routing.len() != 9 {
+        return false;
+    }
+    let district = two_digits(&digits, 0);
+    if !matches!(district, 0..=12 | 21..=32 | 61..=72 | 80) {
+        return false;
+    }
+    let sum: u32 = digits
+        .iter()
+        .zip([3, 7, 1].iter().cycle())
+        .map(|(d, w)| u32::from(d - b'0') * w)
+        .sum();
+    sum.is_multiple_of(10)
+}
+
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+// Bitcoin Base58Check (P2PKH 1..., P2SH 3..., testnet m/n/2): ถอดได้ 25 ไบต์
+// และ 4 ไบต์ท้ายต้องเท่ากับ double SHA-256 ของ 21 ไบต์แรก
+pub fn is_base58check_address(address: &str) -> bool {
+    if !(26..=35).contains(&address.len()) {
+        return false;
+    }
+    let mut bytes = [0u8; 25];
+    for c in address.bytes() {
+        let Some(digit) = BASE58_ALPHABET.iter().position(|&a| a == c) else {
+            return false;
+        };
+        let mut carry = digit as u32;
+        for b in bytes.iter_mut().rev() {
+            carry += u32::from(*b) * 58;
+            *b = carry as u8;
+            carry >>= 8;
+        }
+        if carry != 0 {
+            return false;
+        }
+    }
+    // '1' นำหน้าแต่ละตัวแทนไบต์ 0 หนึ่งไบต์
+    let ones = address.bytes().take_while(|&c| c == b'1').count();
+    let zeros = bytes.iter().take_while(|&&b| b == 0).count();
+    if ones != zeros || !matches!(bytes[0], 0x00 | 0x05 | 0x6F | 0xC4) {
+        return false;
+    }
+    let once = ring::digest::digest(&ring::digest::SHA256, &bytes[..21]);
+    let twice = ring::digest::digest(&ring::digest::SHA256, once.as_ref());
+    twice.as_ref()[..4] == bytes[21..]
+}
+
+const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32M_CONST: u32 = 0x2BC8_30A3;
+
+fn bech32_polymod(values: impl Iterator<Item = u8>) -> u32 {
+    const GEN: [u32; 5] = [0x3B6A_57B2, 0x2650_8E6D, 0x1EA1_19FA, 0x3D42_33DD, 0x2A14_62B3];
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01FF_FFFF) << 5) ^ u32::from(value);
+        for (i, g) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+// Bitcoin SegWit: Bech32 (bc1q..., BIP-173) สำหรับ witness v0 และ Bech32m (bc1p..., BIP-350) สำหรับ v1 ขึ้นไป
+// ตัวพิมพ์ต้องเหมือนกันทั้งหมด และ checksum 6 ตัวท้ายต้องผ่าน polymod
+pub fn is_bech32_address(address: &str) -> bool {
+    let has_lower = address.bytes().any(|b| b.is_ascii_lowercase());
+    let has_upper = address.bytes().any(|b| b.is_ascii_uppercase());
+    if !(14..=90).contains(&address.len()) || has_lower && has_upper {
+        return false;
+    }
+    let address = address.to_ascii_lowercase();
+    let Some((hrp, data)) = address.rsplit_once('1') else {
+        return false;
+    };
+    if hrp != "bc" && hrp != "tb" {
+        return false;
+    }
+    let Some(values) = data
+        .bytes()
+        .map(|c| BECH32_CHARSET.iter().position(|&a| a == c).map(|v| v as u8))
+        .collect::<Option<Vec<u8>>>()
+    else {
+        return false;
+    };
+    if values.len() < 7 || values[0] > 16 {
+        return false;
+    }
+    // ความยาวโปรแกรม: v0 ต้อง 20 หรือ 32 ไบต์ เวอร์ชันอื่น 2-40 ไบต์
+    let program_bytes = (values.len() - 7) * 5 / 8;
+    let length_ok = match values[0] {
+        0 => program_bytes == 20 || program_bytes == 32,
+        _ => (2..=40).contains(&program_bytes),
+    };
+    let expanded = hrp.bytes().map(|c| c >> 5).chain([0]).chain(hrp.bytes().map(|c| c & 31));
+    let expected = if values[0] == 0 { 1 } else { BECH32M_CONST };
+    length_ok && bech32_polymod(expanded.chain(values.iter().copied())) == expected
+}
+
+const KECCAK_ROUND_CONSTANTS: [u64; 24] = [
+    0x0000_0000_0000_0001, 0x0000_0000_0000_8082, 0x8000_0000_0000_808A, 0x8000_0000_8000_8000,
+    0x0000_0000_0000_808B, 0x0000_0000_8000_0001, 0x8000_0000_8000_8081, 0x8000_0000_0000_8009,
+    0x0000_0000_0000_008A, 0x0000_0000_0000_0088, 0x0000_0000_8000_8009, 0x0000_0000_8000_000A,
+    0x0000_0000_8000_808B, 0x8000_0000_0000_008B, 0x8000_0000_0000_8089, 0x8000_0000_0000_8003,
+    0x8000_0000_0000_8002, 0x8000_0000_0000_0080, 0x0000_0000_0000_800A, 0x8000_0000_8000_000A,
+    0x8000_0000_8000_8081, 0x8000_0000_0000_8080, 0x0000_0000_8000_0001, 0x8000_0000_8000_8008,
+];
+const KECCAK_ROTATIONS: [u32; 24] = [1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44];
+const KECCAK_LANES: [usize; 24] = [10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1];
+
+fn keccak_f(state: &mut [u64; 25]) {
+    for round_constant in KECCAK_ROUND_CONSTANTS {
+        let columns: [u64; 5] = std::array::from_fn(|x| (0..5).fold(0, |acc, y| acc ^ state[x + 5 * y]));
+        for x in 0..5 {
+            let d = columns[(x + 4) % 5] ^ columns[(x + 1) % 5].rotate_left(1);
+            for y in 0..5 {
+                state[x + 5 * y] ^= d;
+            }
+        }
+        let mut carried = state[1];
+        for (&lane, &rotation) in KECCAK_LANES.iter().zip(&KECCAK_ROTATIONS) {
+            let next = state[lane];
+            state[lane] = carried.rotate_left(rotation);
+            carried = next;
+        }
+        for y in 0..5 {
+            let row: [u64; 5] = std::array::from_fn(|x| state[x + 5 * y]);
+            for x in 0..5 {
+                state[x + 5 * y] = row[x] ^ (!row[(x + 1) % 5] & row[(x + 2) % 5]);
+            }
+        }
+        state[0] ^= round_constant;
+    }
+}
+
+// Keccak-256 แบบที่ Ethereum ใช้ (padding 0x01 ไม่ใช่ SHA3-256 ของ NIST)
+fn keccak256(data: &[u8]) -> [u8; 32] {
+    const RATE: usize = 136;
+    let mut state = [0u64; 25];
+    let mut padded = data.to_vec();
+    padded.push(0x01);
+    padded.resize(padded.len().div_ceil(RATE) * RATE, 0);
+    if let Some(last) = padded.last_mut() {
+        *last |= 0x80;
+    }
+    for block in padded.chunks(RATE) {
+        for (lane, bytes) in state.iter_mut().zip(block.chunks(8)) {
+            *lane ^= u64::from_le_bytes(bytes.try_into().unwrap_or([0; 8]));
+        }
+        keccak_f(&mut state);
+    }
+    let mut hash = [0u8; 32];
+    for (out, lane) in hash.chunks_mut(8).zip(state) {
+        out.copy_from_slice(&lane.to_le_bytes());
+    }
+    hash
+}
+
+// Ethereum EIP-55: ตัวอักษร a-f เป็นตัวใหญ่ตรงตำแหน่งที่ nibble ของ Keccak-256(ที่อยู่ตัวเล็ก) >= 8
+// ที่อยู่ตัวเล็กหรือตัวใหญ่ล้วนไม่มี checksum จึงไม่ผ่าน
+pub fn is_eip55_address(address: &str) -> bool {
+    let Some(hex) = address.strip_prefix("0x") else {
+        return false;
+    };
+    if hex.len() != 40 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return false;
+    }
+    let has_lower = hex.bytes().any(|b| b.is_ascii_lowercase());
+    let has_upper = hex.bytes().any(|b| b.is_ascii_uppercase());
+    if !(has_lower && has_upper) {
+        return false;
+    }
+    let hash = keccak256(hex.to_ascii_lowercase().as_bytes());
+    hex.bytes().enumerate().all(|(i, b)| {
+        let nibble = if i % 2 == 0 { hash[i / 2] >> 4 } else { hash[i / 2] & 0x0F };
+        !b.is_ascii_alphabetic() || b.is_ascii_uppercase() == (nibble >= 8)
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -54,4 +437,65 @@ mod tests {
     fn test_invalid_thai_id() {
         assert!(!is_thai_id("1103700012345")); // เลขที่ checksum ผิด
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sg_nric_check_letter() {
+        assert!(is_sg_nric("S1234567D"));
+        assert!(!is_sg_nric("S1234567A"));
+        assert!(is_sg_nric("T1234567J"));
+        assert!(is_sg_nric("F1234567N"));
+    }
+
+    #[test]
+    fn test_asean_structured_ids() {
+        assert!(is_my_mykad("900101-14-5678"));
+        assert!(!is_my_mykad("901301-14-5678")); // เดือน 13
+        assert!(!is_my_mykad("900101-17-5678")); // รหัสรัฐไม่มีจริง
+        assert!(is_id_nik("3174054508900001")); // ผู้หญิง วันที่ 05+40
+        assert!(!is_id_nik("0074050508900001"));
+        assert!(is_vn_cccd("001203012345"));
+        assert!(!is_vn_cccd("003203012345"));
+    }
+
+    #[test]
+    fn test_card_networks() {
+        assert_eq!(card_network("4532015112830366"), Some(CardNetwork::Visa));
+        assert_eq!(card_network("5555555555554444"), Some(CardNetwork::Mastercard));
+        assert_eq!(card_network("2221000000000009"), Some(CardNetwork::Mastercard));
+        assert_eq!(card_network("378282246310005"), Some(CardNetwork::Amex));
+        assert_eq!(card_network("3530111333300000"), Some(CardNetwork::Jcb));
+        assert_eq!(card_network("6200000000000005"), Some(CardNetwork::UnionPay));
+        assert_eq!(card_network("37828224631000"), None); // Amex ต้อง 15 หลัก
+        assert!(!is_card_number("1000000000000008")); // ผ่าน Luhn แต่ไม่มีเครือข่ายนี้
+        assert!(is_card_number("4532-0151-1283-0366"));
+    }
+
+    #[test]
+    fn test_international_bank_identifiers() {
+        assert!(is_iban("GB82 WEST 1234 5698 7654 32"));
+        assert!(is_iban("DE89370400440532013000"));
+        assert!(!is_iban("GB82 WEST 1234 5698 7654 33")); // mod-97 ผิด
+        assert!(!is_iban("DE8937040044053201300")); // ความยาวไม่ตรงประเทศ
+        assert!(is_swift_bic("BKKBTHBK"));
+        assert!(is_swift_bic("DEUTDEFF500"));
+        assert!(!is_swift_bic("DATAQQSE")); // QQ ไม่ใช่รหัสประเทศ
+        assert!(is_aba_routing("021000021"));
+        assert!(!is_aba_routing("021000022"));
+    }
+
+    #[test]
+    fn test_crypto_wallet_checksums() {
+        assert!(is_base58check_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa"));
+        assert!(is_base58check_address("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy"));
+        assert!(!is_base58check_address("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb")); // checksum ผิด
+        assert!(is_bech32_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"));
+        assert!(is_bech32_address("bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0"));
+        assert!(!is_bech32_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdd"));
+        assert!(!is_bech32_address("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzWF5MDQ")); // ตัวพิมพ์ปนกัน
+        assert_eq!(keccak256(b"")[..4], [0xC5, 0xD2, 0x46, 0x01]); // c5d2460186f7233c...
+        assert!(is_eip55_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+        assert!(is_eip55_address("0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"));
+        assert!(!is_eip55_address("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(!is_eip55_address("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")); // ไม่มี checksum
+    }
+}
@@ -0,0 +1,124 @@
+use crate::normalize::context_before;
+use crate::validator;
+use lazy_static::lazy_static;
+use regex::Regex;
+
+lazy_static! {
+    // Bitcoin Base58Check, mainnet and testnet: 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa, 3J98t1Wp...
+    static ref RE_BTC_BASE58: Regex = Regex::new(r"[13mn2][1-9A-HJ-NP-Za-km-z]{25,34}").unwrap();
+
+    // Bitcoin SegWit Bech32/Bech32m: bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq
+    static ref RE_BTC_BECH32: Regex = Regex::new(r"(?i)(?:bc|tb)1[02-9ac-hj-np-z]{11,87}").unwrap();
+
+    // Ethereum: 0x + 40 hex digits
+    static ref RE_ETH: Regex = Regex::new(r"0x[0-9a-fA-F]{40}").unwrap();
+
+    // All-lowercase (or all-uppercase) Ethereum addresses carry no checksum; taken only after a keyword
+    static ref RE_ETH_CONTEXT: Regex =
+        Regex::new(r"(?i)(\beth\b|ethereum|wallet|\baddr|erc-?20|กระเป๋า)").unwrap();
+}
+
+/// Whether an address's checksum holds.
+type Checksum = fn(&str) -> bool;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum WalletKind {
+    Bitcoin,
+    Ethereum,
+}
+
+impl WalletKind {
+    /// Detector that finds this kind, as named in the config.
+    pub fn detector(self) -> &'static str {
+        match self {
+            WalletKind::Bitcoin => "bitcoin_address",
+            WalletKind::Ethereum => "ethereum_address",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct WalletMatch {
+    pub start: usize,
+    pub end: usize,
+    pub kind: WalletKind,
+    /// The checksum was verified; false for an all-lowercase Ethereum address taken on a keyword
+    pub checksummed: bool,
+}
+
+/// Finds Bitcoin (Base58Check, Bech32/Bech32m) and Ethereum (EIP-55) addresses whose checksum
+/// holds, earliest first and never overlapping.
+pub fn find_wallets(text: &str, bitcoin: bool, ethereum: bool) -> Vec<WalletMatch> {
+    let rules: [(bool, WalletKind, &Regex, Checksum); 3] = [
+        (bitcoin, WalletKind::Bitcoin, &RE_BTC_BECH32, validator::is_bech32_address),
+        (bitcoin, WalletKind::Bitcoin, &RE_BTC_BASE58, validator::is_base58check_address),
+        (ethereum, WalletKind::Ethereum, &RE_ETH, validator::is_eip55_address),
+    ];
+
+    let bytes = text.as_bytes();
+    let mut found: Vec<WalletMatch> = Vec::new();
+    for (enabled, kind, re, is_valid) in rules {
+        if !enabled || kind == WalletKind::Ethereum && !text.contains("0x") {
+            continue;
+        }
+        for mat in re.find_iter(text) {
+            let glued = mat.start() > 0 && bytes[mat.start() - 1].is_ascii_alphanumeric()
+                || bytes.get(mat.end()).is_some_and(|b| b.is_ascii_alphanumeric());
+            if glued || found.iter().any(|f| f.start < mat.end() && mat.start() < f.end) {
+                continue;
+            }
+            let hex = &mat.as_str()[2..];
+            let unchecked_eth = kind == WalletKind::Ethereum
+                && (hex == hex.to_ascii_lowercase() || hex == hex.to_ascii_uppercase())
+                && RE_ETH_CONTEXT.is_match(context_before(text, mat.start()));
+            let checksummed = is_valid(mat.as_str());
+            if checksummed || unchecked_eth {
+                found.push(WalletMatch {
+                    start: mat.start(),
+                    end: mat.end(),
+                    kind,
+                    checksummed,
+                });
+            }
+        }
+    }
+    found.sort_by_key(|m| m.start);
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(text: &str) -> Vec<(&str, WalletKind)> {
+        find_wallets(text, true, true)
+            .iter()
+            .map(|m| (&text[m.start..m.end], m.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_bitcoin_addresses() {
+        assert_eq!(
+            found("to 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa or bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"),
+            vec![
+                ("1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa", WalletKind::Bitcoin),
+                ("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", WalletKind::Bitcoin)
+            ]
+        );
+        // One character off: checksum fails
+        assert!(found("to 1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNb").is_empty());
+    }
+
+    #[test]
+    fn test_ethereum_addresses() {
+        assert_eq!(
+            found("from 0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"),
+            vec![("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", WalletKind::Ethereum)]
+        );
+        // Random hex: lowercase needs a keyword, mixed case needs a valid checksum
+        assert!(found("hash 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").is_empty());
+        assert!(found("hash 0x5AaEb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_empty());
+        assert_eq!(found("ETH wallet: 0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed").len(), 1);
+    }
+}